@@ -0,0 +1,41 @@
+use std::fs;
+use std::hint::black_box;
+use criterion::{criterion_group, criterion_main, Criterion};
+use common::Solution;
+
+// Benchmark parsing and each part separately, against the day's real input.
+// Parts are measured on an input that was parsed up front.
+fn bench_day<S: Solution>(c: &mut Criterion, name: &str, path: &str) {
+    let data = fs::read_to_string(path).unwrap();
+    let input = S::parse(&data).unwrap();
+    let config = S::Config::default();
+
+    let mut group = c.benchmark_group(name);
+    // Some parts take over a second per run
+    group.sample_size(10);
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(&data)).unwrap()));
+    group.bench_function("part_1", |b| b.iter(|| S::part1(black_box(&input), &config)));
+    group.bench_function("part_2", |b| b.iter(|| S::part2(black_box(&input), &config)));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day01::Day01>(c, "day01", day01::DEFAULT_INPUT);
+    bench_day::<day02::Day02>(c, "day02", day02::DEFAULT_INPUT);
+    bench_day::<day03::Day03>(c, "day03", day03::DEFAULT_INPUT);
+    bench_day::<day04::Day04>(c, "day04", day04::DEFAULT_INPUT);
+    bench_day::<day05::Day05>(c, "day05", day05::DEFAULT_INPUT);
+    bench_day::<day06::Day06>(c, "day06", day06::DEFAULT_INPUT);
+    bench_day::<day07::Day07>(c, "day07", day07::DEFAULT_INPUT);
+    bench_day::<day08::Day08>(c, "day08", day08::DEFAULT_INPUT);
+    bench_day::<day09::Day09>(c, "day09", day09::DEFAULT_INPUT);
+    bench_day::<day10::Day10>(c, "day10", day10::DEFAULT_INPUT);
+    bench_day::<day11::Day11>(c, "day11", day11::DEFAULT_INPUT);
+    bench_day::<day12::Day12>(c, "day12", day12::DEFAULT_INPUT);
+    bench_day::<day13::Day13>(c, "day13", day13::DEFAULT_INPUT);
+    bench_day::<day14::Day14>(c, "day14", day14::DEFAULT_INPUT);
+    bench_day::<day15::Day15>(c, "day15", day15::DEFAULT_INPUT);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
@@ -0,0 +1,64 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// The system allocator, keeping count of how much heap each thread has in
+/// use so a solver's peak memory can be measured.
+pub struct TrackingAlloc;
+
+thread_local! {
+    static CURRENT: Cell<isize> = const { Cell::new(0) };
+    static PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+fn grow(delta: isize) {
+    // Thread locals may already be gone while a thread is shutting down
+    let _ = CURRENT.try_with(|c| {
+        let n = c.get() + delta;
+        c.set(n);
+        let _ = PEAK.try_with(|p| if n > p.get() { p.set(n) });
+    });
+}
+
+unsafe impl GlobalAlloc for TrackingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc(layout);
+        if !p.is_null() {
+            grow(layout.size() as isize);
+        }
+        p
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let p = System.alloc_zeroed(layout);
+        if !p.is_null() {
+            grow(layout.size() as isize);
+        }
+        p
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        grow(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let p = System.realloc(ptr, layout, new_size);
+        if !p.is_null() {
+            grow(new_size as isize - layout.size() as isize);
+        }
+        p
+    }
+}
+
+/// Run `f`, returning the most heap (in bytes) it had in use at once on
+/// this thread, on top of what was already allocated
+pub fn peak_heap<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = CURRENT.with(|c| c.get());
+    let outer_peak = PEAK.with(|p| p.replace(start));
+
+    let res = f();
+
+    let peak = PEAK.with(|p| p.get());
+    PEAK.with(|p| p.set(outer_peak.max(peak)));
+    (res, (peak - start) as usize)
+}
@@ -0,0 +1,67 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use common::Answer;
+
+/// Answers known to be right, keyed by day, part, the checksum of the input
+/// they were computed from and the puzzle parameters they were computed
+/// with. Kept in a TOML file so they can be checked in and compared against
+/// after refactoring a solver.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnswerStore {
+    #[serde(default, rename = "answer")]
+    answers: Vec<Recorded>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recorded {
+    day: u8,
+    part: u8,
+    input: String,
+    // The parameters' fingerprint, empty for the real puzzle's
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    config: String,
+    answer: String,
+}
+
+impl Recorded {
+    fn is(&self, day: u8, part: u8, input: &str, config: &str) -> bool {
+        self.day == day && self.part == part && self.input == input && self.config == config
+    }
+}
+
+impl AnswerStore {
+    /// A missing file is an empty store
+    pub fn load(path: &Path) -> io::Result<AnswerStore> {
+        match fs::read_to_string(path) {
+            Ok(s) => toml::from_str(&s).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AnswerStore::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let s = toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, s)
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str, config: &str) -> Option<&str> {
+        self.answers.iter()
+            .find(|r| r.is(day, part, input, config))
+            .map(|r| r.answer.as_str())
+    }
+
+    /// Record (or replace) the answer for a day and part of an input, with
+    /// the parameters fingerprinted as `config`
+    pub fn record(&mut self, day: u8, part: u8, input: &str, config: &str, answer: &Answer) {
+        let answer = answer.to_string();
+        match self.answers.iter_mut().find(|r| r.is(day, part, input, config)) {
+            Some(r) => r.answer = answer,
+            None => {
+                self.answers.push(Recorded { day, part, input: input.to_string(), config: config.to_string(), answer });
+                self.answers.sort_by(|a, b| (a.day, a.part, &a.input, &a.config).cmp(&(b.day, b.part, &b.input, &b.config)));
+            },
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use crate::report::DayReport;
+
+const HEADER: [&str; 10] = [
+    "file", "status", "parse_ms", "part_1", "part_1_ms", "part_2", "part_2_ms",
+    "peak_heap_bytes", "input_checksum", "error",
+];
+
+/// What running a day on one file of a batch produced
+pub struct Row {
+    pub file: PathBuf,
+    pub outcome: Result<DayReport, String>,
+}
+
+/// The inputs in `dir`, in name order. Hidden files are left out, and so are
+/// config files (`.toml`), which go with the input of the same name.
+pub fn inputs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.'));
+        let config = path.extension().is_some_and(|e| e == "toml");
+        if path.is_file() && !hidden && !config {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn ms(report: &DayReport, part: Option<u8>) -> String {
+    let elapsed = match part {
+        None => Some(report.run.parse_time),
+        Some(n) => report.run.results.iter().find(|r| r.part == n).map(|r| r.elapsed),
+    };
+    elapsed.map_or(String::new(), |d| format!("{:.3}", d.as_secs_f64() * 1000.0))
+}
+
+fn answer(report: &DayReport, part: u8) -> String {
+    report.run.results.iter()
+        .find(|r| r.part == part)
+        .map_or(String::new(), |r| r.answer.to_string())
+}
+
+// Quoted (with quotes doubled) if it has anything CSV would trip over.
+// Images are answers too, so that includes newlines.
+fn field(s: &str) -> Cow<'_, str> {
+    if s.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+/// One line per file, after a header. Parts that weren't run are left
+/// empty, and so is everything but the error for a file that failed.
+pub fn csv(rows: &[Row]) -> String {
+    let mut out = String::new();
+    writeln!(out, "{}", HEADER.join(",")).unwrap();
+
+    for row in rows {
+        let file = row.file.display().to_string();
+        let fields = match &row.outcome {
+            Ok(r) => vec![
+                file, "ok".to_string(), ms(r, None),
+                answer(r, 1), ms(r, Some(1)), answer(r, 2), ms(r, Some(2)),
+                r.peak_heap.to_string(), r.input_checksum.clone(), String::new(),
+            ],
+            Err(e) => {
+                let mut fields = vec![String::new(); HEADER.len()];
+                fields[0] = file;
+                fields[1] = "error".to_string();
+                fields[HEADER.len() - 1] = e.clone();
+                fields
+            },
+        };
+
+        writeln!(out, "{}", fields.iter().map(|f| field(f)).collect::<Vec<_>>().join(",")).unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use common::{Answer, PartResult, Run};
+
+    #[test]
+    fn rows() {
+        let run = Run {
+            parse_time: Duration::from_micros(1500),
+            results: vec![
+                PartResult { part: 1, answer: Answer::Int(42), elapsed: Duration::from_millis(2) },
+                PartResult { part: 2, answer: Answer::Image("#.\n.\"#".to_string()), elapsed: Duration::ZERO },
+            ],
+        };
+        let rows = [
+            Row {
+                file: PathBuf::from("in/a.txt"),
+                outcome: Ok(DayReport { day: 10, input_checksum: "00ff".to_string(), run, peak_heap: 1024 }),
+            },
+            Row { file: PathBuf::from("in/b.txt"), outcome: Err("in/b.txt:3:1: expected a number, found \"x\"".to_string()) },
+        ];
+
+        let expected = "\
+file,status,parse_ms,part_1,part_1_ms,part_2,part_2_ms,peak_heap_bytes,input_checksum,error
+in/a.txt,ok,1.500,42,2.000,\"#.\n.\"\"#\",0.000,1024,00ff,
+in/b.txt,error,,,,,,,,\"in/b.txt:3:1: expected a number, found \"\"x\"\"\"
+";
+        assert_eq!(csv(&rows), expected);
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use common::geometry::Point2;
+
+/// Days there's an input generator for
+pub const DAYS: [u8; 8] = [1, 5, 7, 9, 11, 12, 13, 15];
+
+// The search area day 15's part 2 looks for the gap in
+const DAY15_MAX: isize = 4000000;
+
+/// A random, valid, input for `day`, or `None` if there's no generator for
+/// it. `size` is roughly how many things (elves, moves, files, ...) go in;
+/// the same seed always gives the same input.
+pub fn generate(day: u8, size: usize, seed: u64) -> Option<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let size = size.max(1);
+    let input = match day {
+        1 => day01(size, &mut rng),
+        5 => day05(size, &mut rng),
+        7 => day07(size, &mut rng),
+        9 => day09(size, &mut rng),
+        11 => day11(size, &mut rng),
+        12 => day12(size, &mut rng),
+        13 => day13(size, &mut rng),
+        15 => day15(size, &mut rng, DAY15_MAX),
+        _ => return None,
+    };
+    Some(input)
+}
+
+// `size` elves, each carrying a handful of snacks
+fn day01(size: usize, rng: &mut StdRng) -> String {
+    let mut out = String::new();
+    for i in 0..size {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        for _ in 0..rng.random_range(1..=15) {
+            writeln!(out, "{}", rng.random_range(1000..=70000)).unwrap();
+        }
+    }
+    out
+}
+
+// Nine stacks and `size` moves, each moving crates that are really there
+fn day05(size: usize, rng: &mut StdRng) -> String {
+    // Every stack needs a crate to start with, or the parser won't know of it
+    let mut stacks = (0..9)
+        .map(|_| (0..rng.random_range(1..=8)).map(|_| rng.random_range('A'..='Z')).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    for row in (0..height).rev() {
+        let line = stacks.iter()
+            .map(|s| s.get(row).map(|c| format!("[{}]", c)).unwrap_or_else(|| "   ".to_string()))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", line.join(" ")).unwrap();
+    }
+    let numbers = (1..=stacks.len()).map(|i| format!(" {} ", i)).collect::<Vec<_>>();
+    writeln!(out, "{}\n", numbers.join(" ")).unwrap();
+
+    for _ in 0..size {
+        let full = (0..stacks.len()).filter(|&i| !stacks[i].is_empty()).collect::<Vec<_>>();
+        let from = *full.choose(rng).unwrap();
+        let to = (from + rng.random_range(1..stacks.len())) % stacks.len();
+        let quantity = rng.random_range(1..=stacks[from].len());
+
+        let at = stacks[from].len() - quantity;
+        let moved = stacks[from].split_off(at);
+        stacks[to].extend(moved);
+        writeln!(out, "move {} from {} to {}", quantity, from + 1, to + 1).unwrap();
+    }
+    out
+}
+
+struct Dir {
+    name: String,
+    dirs: Vec<usize>,
+    files: Vec<(String, u64)>,
+}
+
+// A tree of `size` files spread over about a quarter as many directories.
+// They add up to enough that part 2 has something to delete, but still fit
+// on the disk.
+fn day07(size: usize, rng: &mut StdRng) -> String {
+    let mut dirs = vec![Dir { name: "/".to_string(), dirs: Vec::new(), files: Vec::new() }];
+    let mut names = vec![HashSet::new()];
+
+    for _ in 0..size / 4 {
+        let parent = rng.random_range(0..dirs.len());
+        let name = unique_name(rng, &mut names[parent], false);
+        dirs.push(Dir { name, dirs: Vec::new(), files: Vec::new() });
+        names.push(HashSet::new());
+        let i = dirs.len() - 1;
+        dirs[parent].dirs.push(i);
+    }
+
+    let weights = (0..size).map(|_| rng.random_range(1..=300000u64)).collect::<Vec<_>>();
+    let total = rng.random_range(45000000..=65000000u64);
+    let sum = weights.iter().sum::<u64>();
+    for w in weights {
+        let dir = rng.random_range(0..dirs.len());
+        let name = unique_name(rng, &mut names[dir], true);
+        dirs[dir].files.push((name, (w * total / sum).max(1)));
+    }
+
+    let mut out = String::from("$ cd /\n");
+    write_dir(&mut out, &dirs, 0);
+    out
+}
+
+fn unique_name(rng: &mut StdRng, taken: &mut HashSet<String>, extension: bool) -> String {
+    loop {
+        let mut name = (0..rng.random_range(1..=8)).map(|_| rng.random_range('a'..='z')).collect::<String>();
+        if extension && rng.random_bool(0.5) {
+            name.push('.');
+            name.extend((0..3).map(|_| rng.random_range('a'..='z')));
+        }
+
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
+
+fn write_dir(out: &mut String, dirs: &[Dir], i: usize) {
+    writeln!(out, "$ ls").unwrap();
+    for &d in dirs[i].dirs.iter() {
+        writeln!(out, "dir {}", dirs[d].name).unwrap();
+    }
+    for (name, size) in dirs[i].files.iter() {
+        writeln!(out, "{} {}", size, name).unwrap();
+    }
+
+    for &d in dirs[i].dirs.iter() {
+        writeln!(out, "$ cd {}", dirs[d].name).unwrap();
+        write_dir(out, dirs, d);
+        writeln!(out, "$ cd ..").unwrap();
+    }
+}
+
+// `size` moves of the rope's head
+fn day09(size: usize, rng: &mut StdRng) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let dir = ['U', 'D', 'L', 'R'].choose(rng).unwrap();
+        writeln!(out, "{} {}", dir, rng.random_range(1..=20)).unwrap();
+    }
+    out
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    operation: Operation,
+    divisor: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Add(u64),
+    Mult(u64),
+    Square,
+}
+
+impl Operation {
+    fn apply(self, old: u64) -> Option<u64> {
+        match self {
+            Operation::Add(n) => old.checked_add(n),
+            Operation::Mult(n) => old.checked_mul(n),
+            Operation::Square => old.checked_mul(old),
+        }
+    }
+}
+
+// `size` monkeys, up to 8. Their divisors are distinct primes, so the worry
+// levels can be reduced modulo their product without overflowing.
+fn day11(size: usize, rng: &mut StdRng) -> String {
+    // Part 1 can't reduce worry levels, and squaring them soon overflows if
+    // an item keeps coming back to the monkey doing it. Try until it doesn't.
+    let monkeys = loop {
+        let monkeys = monkeys(size.clamp(2, 8), rng);
+        if part_1_fits(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    monkeys.iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items = m.items.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            let operation = match m.operation {
+                Operation::Add(n) => format!("old + {}", n),
+                Operation::Mult(n) => format!("old * {}", n),
+                Operation::Square => "old * old".to_string(),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i, items.join(", "), operation, m.divisor, m.if_true, m.if_false)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn monkeys(count: usize, rng: &mut StdRng) -> Vec<Monkey> {
+    let mut primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    primes.shuffle(rng);
+    let squarer = rng.random_range(0..count);
+
+    (0..count)
+        .map(|i| {
+            let items = (0..rng.random_range(1..=8)).map(|_| rng.random_range(50..=99)).collect();
+            let operation = if i == squarer {
+                Operation::Square
+            } else if rng.random_bool(0.5) {
+                Operation::Add(rng.random_range(1..=8))
+            } else {
+                Operation::Mult(rng.random_range(2..=19))
+            };
+
+            let others = (0..count).filter(|&m| m != i).collect::<Vec<_>>();
+            let if_true = *others.choose(rng).unwrap();
+            let mut if_false = *others.choose(rng).unwrap();
+            while if_false == if_true && others.len() > 1 {
+                if_false = *others.choose(rng).unwrap();
+            }
+
+            Monkey { items, operation, divisor: primes[i], if_true, if_false }
+        })
+        .collect()
+}
+
+// Play part 1's 20 rounds, checking nothing overflows
+fn part_1_fits(monkeys: &[Monkey]) -> bool {
+    let mut items = monkeys.iter().map(|m| m.items.clone()).collect::<Vec<_>>();
+    for _ in 0..20 {
+        for (i, m) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[i]) {
+                let Some(worry) = m.operation.apply(item) else {
+                    return false;
+                };
+                let worry = worry / 3;
+                let to = if worry % m.divisor == 0 { m.if_true } else { m.if_false };
+                items[to].push(worry);
+            }
+        }
+    }
+    true
+}
+
+// A height map `size` columns wide (at least 30), with a route from S to E
+// climbing at most one step at a time carved through random heights
+fn day12(size: usize, rng: &mut StdRng) -> String {
+    let width = size.max(30);
+    let height = width / 4 + 5;
+    let mut grid = (0..height)
+        .map(|_| (0..width).map(|_| rng.random_range(b'a'..=b'z')).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // Left to right, wandering up or down in each column. Never revisits a
+    // cell, and it's at least 30 long, long enough to get from a to z.
+    let mut route = Vec::new();
+    let mut y = rng.random_range(0..height);
+    for x in 0..width {
+        let to = rng.random_range(0..height);
+        route.push(Point2::new(x, y));
+        while y != to {
+            y = if to > y { y + 1 } else { y - 1 };
+            route.push(Point2::new(x, y));
+        }
+    }
+
+    let last = route.len() - 1;
+    for (i, p) in route.iter().enumerate() {
+        grid[p.y][p.x] = match i {
+            0 => b'S',
+            i if i == last => b'E',
+            i => b'a' + i.min(26) as u8 - 1,
+        };
+    }
+
+    grid.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+// `size` pairs of packets
+fn day13(size: usize, rng: &mut StdRng) -> String {
+    (0..size)
+        .map(|_| format!("{}\n{}\n", not_divider(rng), not_divider(rng)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// A packet that doesn't compare equal to part 2's [[2]] and [[6]] dividers,
+// which would make where they're sorted to ambiguous
+fn not_divider(rng: &mut StdRng) -> String {
+    loop {
+        let p = packet(rng, 0);
+        if !matches!(p.replace(['[', ']'], "").as_str(), "2" | "6") {
+            return p;
+        }
+    }
+}
+
+fn packet(rng: &mut StdRng, depth: usize) -> String {
+    let items = (0..rng.random_range(0..=5))
+        .map(|_| if depth < 4 && rng.random_bool(0.3) {
+            packet(rng, depth + 1)
+        } else {
+            rng.random_range(0..=10).to_string()
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(","))
+}
+
+// About `size` sensors, covering all of the square from (0, 0) to (max, max)
+// but for a single position
+fn day15(size: usize, rng: &mut StdRng, max: isize) -> String {
+    let (_, sensors) = day15_sensors(size, rng, max);
+
+    let mut out = String::new();
+    for (s, range) in sensors {
+        // Anywhere at the edge of the sensor's range
+        let dx = between(rng, 0, range);
+        let dy = range - dx;
+        let bx = if rng.random_bool(0.5) { s.x + dx } else { s.x - dx };
+        let by = if rng.random_bool(0.5) { s.y + dy } else { s.y - dy };
+        writeln!(out, "Sensor at x={}, y={}: closest beacon is at x={}, y={}", s.x, s.y, bx, by).unwrap();
+    }
+    out
+}
+
+type Sensor = (Point2<isize>, isize);
+
+// rand doesn't sample isize
+fn between(rng: &mut StdRng, from: isize, to: isize) -> isize {
+    rng.random_range(from as i64..=to as i64) as isize
+}
+
+// The gap, and sensors (with their range) covering everything else
+fn day15_sensors(size: usize, rng: &mut StdRng, max: isize) -> (Point2<isize>, Vec<Sensor>) {
+    let gap = Point2::new(between(rng, 0, max), between(rng, 0, max));
+
+    // Sensors anywhere, as long as they don't reach the gap
+    let mut sensors = Vec::new();
+    for _ in 0..size {
+        let s = Point2::new(between(rng, 0, max), between(rng, 0, max));
+        let dist = s.manhattan(&gap);
+        if dist >= 2 {
+            sensors.push((s, between(rng, dist / 2, dist - 1)));
+        }
+    }
+
+    // Then plug whatever holes they leave with sensors further out from the
+    // gap than the hole is, reaching up to (but not including) the gap
+    for y in 0..=max {
+        while let Some(x) = uncovered(&sensors, y, max, gap) {
+            let away = between(rng, 1, max / 100 + 1);
+            let s = Point2::new(x + away * (x - gap.x).signum(), y + away * (y - gap.y).signum());
+            sensors.push((s, s.manhattan(&gap) - 1));
+        }
+    }
+
+    (gap, sensors)
+}
+
+// First position on line `y` no sensor covers, other than the gap
+fn uncovered(sensors: &[Sensor], y: isize, max: isize, gap: Point2<isize>) -> Option<isize> {
+    let mut spans = sensors.iter()
+        .filter_map(|(s, range)| {
+            let half = range - (s.y - y).abs();
+            (half >= 0).then_some((s.x - half, s.x + half))
+        })
+        .collect::<Vec<_>>();
+    spans.sort_unstable();
+    spans.push((max + 1, max + 1));
+
+    let mut x = 0;
+    for (from, to) in spans {
+        while x < from && x <= max {
+            if Point2::new(x, y) != gap {
+                return Some(x);
+            }
+            x += 1;
+        }
+        x = x.max(to + 1);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A few the size of the examples, and one bigger than the real input
+    fn inputs(day: u8) -> impl Iterator<Item = String> {
+        (0..5).map(move |seed| generate(day, 40, seed).unwrap())
+            .chain(std::iter::once(generate(day, 1000, 5).unwrap()))
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(9, 10, 7), generate(9, 10, 7));
+        assert_ne!(generate(9, 10, 7), generate(9, 10, 8));
+        assert_eq!(generate(2, 10, 7), None);
+    }
+
+    #[test]
+    fn inputs_round_trip() {
+        for input in inputs(1) {
+            let parsed = day01::parse(&input).unwrap();
+            day01::part_2(&parsed);
+        }
+        for input in inputs(5) {
+            let parsed = day05::parse(&input).unwrap();
+            day05::part_1(&parsed);
+            day05::part_2(&parsed);
+        }
+        for input in inputs(7) {
+            let parsed = day07::parse(&input).unwrap();
+            day07::part_2(&parsed, &Default::default());
+        }
+        for input in inputs(9) {
+            let parsed = day09::parse(&input).unwrap();
+            day09::part_2(&parsed);
+        }
+        for input in inputs(11) {
+            let parsed = day11::parse(&input).unwrap();
+            day11::part_1(&parsed, &Default::default());
+            day11::part_2(&parsed, &Default::default());
+        }
+        for input in inputs(12) {
+            let parsed = day12::parse(&input).unwrap();
+            day12::part_1(&parsed);
+            day12::part_2(&parsed);
+        }
+        for input in inputs(13) {
+            let parsed = day13::parse(&input).unwrap();
+            day13::part_1(&parsed);
+            day13::part_2(&parsed);
+        }
+        let config = day15::Config { interesting_line: 50, max: 100 };
+        for (seed, size) in [(0, 40), (1, 40), (2, 40), (3, 40), (4, 40), (5, 1000)] {
+            let input = day15(size, &mut StdRng::seed_from_u64(seed), config.max);
+            let parsed = day15::parse(&input).unwrap();
+            day15::part_1(&parsed, &config);
+            day15::part_2(&parsed, &config);
+        }
+    }
+
+    #[test]
+    fn day15_has_a_single_gap() {
+        for seed in 0..5 {
+            let max = 60;
+            let (gap, sensors) = day15_sensors(10, &mut StdRng::seed_from_u64(seed), max);
+            for y in 0..=max {
+                for x in 0..=max {
+                    let p = Point2::new(x, y);
+                    let covered = sensors.iter().any(|(s, range)| s.manhattan(&p) <= *range);
+                    assert_eq!(covered, p != gap, "{:?}", p);
+                }
+            }
+        }
+    }
+}
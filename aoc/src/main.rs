@@ -0,0 +1,771 @@
+use std::any::Any;
+use std::io::{self, Read};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process;
+use rayon::prelude::*;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::info_span;
+use common::explore::{Explore, Stepper};
+use common::{print_answers, Answer, Checksummed, ConfigError, InputSource, Params, ParseError, Part, Run, Solution, Validate};
+use answers::AnswerStore;
+use report::DayReport;
+
+mod alloc;
+mod answers;
+mod batch;
+mod gen;
+mod player;
+mod report;
+mod repl;
+mod scaffold;
+
+#[global_allocator]
+static ALLOC: alloc::TrackingAlloc = alloc::TrackingAlloc;
+
+type SolveFn = fn(&str, &Params, Part) -> Result<Run, DayError>;
+
+enum DayError {
+    Config(ConfigError),
+    Parse(ParseError),
+}
+
+struct Day {
+    solve: SolveFn,
+    default_input: &'static str,
+}
+
+macro_rules! day {
+    ($krate:ident, $solution:ident) => {
+        Day { solve: solve::<$krate::$solution>, default_input: $krate::DEFAULT_INPUT }
+    };
+}
+
+fn solve<S: Solution>(input: &str, params: &Params, part: Part) -> Result<Run, DayError> {
+    let config = params.get().map_err(DayError::Config)?;
+    S::solve(input, &config, part).map_err(DayError::Parse)
+}
+
+/// Every day's solution, in day order
+const DAYS: [Day; 15] = [
+    day!(day01, Day01),
+    day!(day02, Day02),
+    day!(day03, Day03),
+    day!(day04, Day04),
+    day!(day05, Day05),
+    day!(day06, Day06),
+    day!(day07, Day07),
+    day!(day08, Day08),
+    day!(day09, Day09),
+    day!(day10, Day10),
+    day!(day11, Day11),
+    day!(day12, Day12),
+    day!(day13, Day13),
+    day!(day14, Day14),
+    day!(day15, Day15),
+];
+
+const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.toml");
+
+/// Run Advent of Code 2022 solutions
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Day to run (all days are run if omitted)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+    day: Option<u8>,
+
+    /// Part to run: 1, 2 or all
+    #[arg(long, global = true, default_value = "all")]
+    part: Part,
+
+    /// Input file, or `-` for stdin (defaults to the day's `src/input.txt`)
+    #[arg(long, global = true, requires = "day")]
+    input: Option<String>,
+
+    /// Also draw the day's state to an image (PNG, or PPM if it ends in
+    /// .ppm). Days 8, 10, 12, 14 and 15 can be drawn.
+    #[arg(long, global = true, requires = "day")]
+    render: Option<PathBuf>,
+
+    /// Puzzle parameters (defaults to the `.toml` file next to the input,
+    /// e.g. `example.toml` for `example.txt`, if there is one)
+    #[arg(long, global = true, requires = "day")]
+    config: Option<PathBuf>,
+
+    /// Override a puzzle parameter, e.g. `--set max=20`
+    #[arg(long = "set", global = true, requires = "day", value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+
+    /// Solve as the input's read, in one pass without holding all of it,
+    /// for inputs too big for memory (days 1, 6, 7, 9 and 10)
+    #[arg(long, global = true, requires = "day")]
+    stream: bool,
+
+    /// Output format
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+
+    /// Log more to stderr: -v for timings, -vv for debugging, -vvv for
+    /// every step (RUST_LOG overrides this)
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run days concurrently, printing their answers in day order
+    RunAll {
+        /// Number of worker threads (defaults to one per CPU)
+        #[arg(long)]
+        jobs: Option<usize>,
+    },
+    /// Check answers against the ones recorded for the same input
+    Verify {
+        /// Record the answers as correct instead of checking them
+        #[arg(long)]
+        record: bool,
+
+        /// File the answers are recorded in
+        #[arg(long, default_value = DEFAULT_ANSWERS)]
+        answers: PathBuf,
+    },
+    /// Play the day's simulation in the terminal (days 5, 9, 10 and 14)
+    Animate {
+        /// Frames a second to start with
+        #[arg(long, default_value_t = 20.0)]
+        speed: f64,
+    },
+    /// Run the day on every file in a directory, printing a CSV of answers,
+    /// timings and errors
+    Batch {
+        /// Directory of inputs
+        dir: PathBuf,
+    },
+    /// Explore the day's parsed input with commands typed in (days 7, 8, 9,
+    /// 10, 11 and 15)
+    Repl,
+    /// Start a crate for a new day, from a template
+    New {
+        /// Add the regex and lazy_static dependencies
+        #[arg(long)]
+        regex: bool,
+    },
+    /// Print a random input for the day
+    Gen {
+        /// Roughly how many things (elves, moves, files, ...) to generate
+        #[arg(long, default_value_t = 100)]
+        size: usize,
+
+        /// Seed for the random generator; the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// Human readable answers, grouped by day
+    Text,
+    /// One JSON object per line for each day and part
+    Json,
+}
+
+/// A single line of `--format json` output
+#[derive(Serialize)]
+struct Record<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a Answer,
+    elapsed_ms: f64,
+    parse_ms: f64,
+    peak_heap_bytes: usize,
+    input_checksum: &'a str,
+}
+
+/// Read a day's input and run the selected parts on it
+fn run_day(day: u8, source: &InputSource, params: &Params, part: Part) -> Result<DayReport, String> {
+    let entry = &DAYS[day as usize - 1];
+    let _span = info_span!("day", day).entered();
+
+    // Read the whole input up front so it can be checksummed
+    let data = read_source(source)?;
+
+    // Anything that isn't UTF-8 is left for the parser to complain about
+    let text = String::from_utf8_lossy(&data);
+    let (run, peak_heap) = alloc::peak_heap(|| (entry.solve)(&text, params, part));
+    let run = run.map_err(|e| match e {
+        DayError::Config(e) => e.to_string(),
+        DayError::Parse(e) => e.with_file(source).to_string(),
+    })?;
+
+    Ok(DayReport { day, input_checksum: common::checksum(&data), run, peak_heap })
+}
+
+// Like `run_day`, but solving as the input's read, for the days that can
+// (see each one's `stream`)
+fn stream_day(day: u8, source: &InputSource, params: &Params, part: Part) -> Result<DayReport, String> {
+    let reader = source.open().map_err(|e| format!("Can't read {}: {}", source, e))?;
+    let mut reader = Checksummed::new(reader);
+    let config_error = |e: ConfigError| e.to_string();
+
+    let (run, peak_heap) = match day {
+        1 => alloc::peak_heap(|| day01::stream(&mut reader, part)),
+        6 => {
+            let config = params.get().map_err(config_error)?;
+            alloc::peak_heap(|| day06::stream(&mut reader, &config, part))
+        },
+        7 => {
+            let config = params.get().map_err(config_error)?;
+            alloc::peak_heap(|| day07::stream(&mut reader, &config, part))
+        },
+        9 => alloc::peak_heap(|| day09::stream(&mut reader, part)),
+        10 => {
+            let config = params.get().map_err(config_error)?;
+            alloc::peak_heap(|| day10::stream(&mut reader, &config, part))
+        },
+        _ => return Err(format!("Day {} can't stream its input (only days 1, 6, 7, 9 and 10)", day)),
+    };
+    let run = run.map_err(|e| e.with_file(source).to_string())?;
+
+    Ok(DayReport { day, input_checksum: reader.checksum(), run, peak_heap })
+}
+
+// Run a day whichever way the arguments say to
+fn report(args: &Args, day: u8, source: &InputSource, params: &Params) -> Result<DayReport, String> {
+    if args.stream {
+        stream_day(day, source, params, args.part)
+    } else {
+        run_day(day, source, params, args.part)
+    }
+}
+
+fn read_source(source: &InputSource) -> Result<Vec<u8>, String> {
+    let mut data = Vec::new();
+    source.open()
+        .and_then(|mut r| r.read_to_end(&mut data))
+        .map_err(|e| format!("Can't read {}: {}", source, e))?;
+    Ok(data)
+}
+
+fn input_source(args: &Args, day: u8) -> InputSource {
+    match &args.input {
+        Some(arg) => InputSource::from_arg(arg),
+        None => InputSource::from_arg(DAYS[day as usize - 1].default_input),
+    }
+}
+
+// The config file given, or the one next to the input, with any overrides
+fn params(args: &Args, source: &InputSource) -> Result<Params, String> {
+    let mut params = match &args.config {
+        Some(path) => Params::load(path),
+        None => Params::for_input(source),
+    }.map_err(|e| e.to_string())?;
+
+    for assignment in args.overrides.iter() {
+        params.set(assignment).map_err(|e| e.to_string())?;
+    }
+
+    Ok(params)
+}
+
+// A day's config, for the commands that call into it directly
+fn config<C: DeserializeOwned + Validate>(args: &Args, source: &InputSource) -> C {
+    params(args, source)
+        .and_then(|p| p.get().map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        })
+}
+
+fn days(args: &Args) -> std::ops::RangeInclusive<u8> {
+    match args.day {
+        Some(d) => d..=d,
+        None => 1..=DAYS.len() as u8,
+    }
+}
+
+fn print_report(format: Format, report: &DayReport) {
+    match format {
+        Format::Text => {
+            println!("=== Day {:02} ===", report.day);
+            print_answers(&report.run.results);
+        },
+        Format::Json => {
+            for r in report.run.results.iter() {
+                let record = Record {
+                    day: report.day,
+                    part: r.part,
+                    answer: &r.answer,
+                    elapsed_ms: r.elapsed.as_secs_f64() * 1000.0,
+                    parse_ms: report.run.parse_time.as_secs_f64() * 1000.0,
+                    peak_heap_bytes: report.peak_heap,
+                    input_checksum: &report.input_checksum,
+                };
+                println!("{}", serde_json::to_string(&record).unwrap());
+            }
+        },
+    }
+}
+
+fn print_summary(format: Format, reports: &[DayReport]) {
+    if format == Format::Text {
+        eprintln!();
+        eprint!("{}", report::summary(reports));
+    }
+}
+
+fn run(args: &Args) {
+    let mut reports = Vec::new();
+    for day in days(args) {
+        let source = input_source(args, day);
+        let report = params(args, &source)
+            .and_then(|params| report(args, day, &source, &params))
+            .unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+
+        print_report(args.format, &report);
+        reports.push(report);
+    }
+
+    print_summary(args.format, &reports);
+}
+
+// Like `run`, but the days run on a thread pool. A day that fails, even by
+// panicking, is reported without stopping the others.
+fn run_all(args: &Args, jobs: Option<usize>) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+        .unwrap();
+
+    let outcomes = pool.install(|| {
+        days(args).collect::<Vec<_>>()
+            .into_par_iter()
+            .map(|day| {
+                let source = input_source(args, day);
+                let outcome = panic::catch_unwind(|| {
+                    params(args, &source).and_then(|params| report(args, day, &source, &params))
+                })
+                    .unwrap_or_else(|e| Err(format!("panicked: {}", panic_message(&*e))));
+                (day, outcome)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let mut reports = Vec::new();
+    let mut failures = 0;
+    for (day, outcome) in outcomes {
+        match outcome {
+            Ok(report) => {
+                print_report(args.format, &report);
+                reports.push(report);
+            },
+            Err(e) => {
+                eprintln!("Day {:02} failed: {}", day, e);
+                failures += 1;
+            },
+        }
+    }
+
+    print_summary(args.format, &reports);
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s
+    } else {
+        "unknown cause"
+    }
+}
+
+// Compare every answer with the recorded one (or record them all). Exits
+// with an error if anything doesn't match.
+fn verify(args: &Args, record: bool, path: &Path) {
+    let mut store = AnswerStore::load(path).unwrap_or_else(|e| {
+        eprintln!("Can't load answers from {}: {}", path.display(), e);
+        process::exit(1);
+    });
+
+    let mut failures = 0;
+    for day in days(args) {
+        let source = input_source(args, day);
+        // Answers depend on the parameters as well as the input
+        let run = params(args, &source)
+            .and_then(|params| Ok((report(args, day, &source, &params)?, params.fingerprint())));
+        let (report, config) = match run {
+            Ok(r) => r,
+            Err(e) => {
+                println!("Day {:02}: FAILED: {}", day, e);
+                failures += 1;
+                continue;
+            },
+        };
+
+        let input_checksum = &report.input_checksum;
+        for r in report.run.results.iter() {
+            if record {
+                store.record(day, r.part, input_checksum, &config, &r.answer);
+                println!("Day {:02} part {}: recorded", day, r.part);
+                continue;
+            }
+
+            match store.get(day, r.part, input_checksum, &config) {
+                Some(expected) if expected == r.answer.to_string() => {
+                    println!("Day {:02} part {}: ok", day, r.part);
+                },
+                Some(expected) => {
+                    println!("Day {:02} part {}: MISMATCH: expected {}, got {}", day, r.part, expected, r.answer);
+                    failures += 1;
+                },
+                None if config.is_empty() => println!("Day {:02} part {}: no recorded answer for input {}", day, r.part, input_checksum),
+                None => println!("Day {:02} part {}: no recorded answer for input {} with {}", day, r.part, input_checksum, config),
+            }
+        }
+    }
+
+    if record {
+        if let Err(e) = store.save(path) {
+            eprintln!("Can't save answers to {}: {}", path.display(), e);
+            process::exit(1);
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{} answer(s) didn't verify", failures);
+        process::exit(1);
+    }
+}
+
+// Day 14 draws part 1's cave for `--part 1`, part 2's otherwise
+fn render(args: &Args, path: &Path) {
+    let day = args.day.unwrap();
+    let source = input_source(args, day);
+    let data = read_source(&source).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let text = String::from_utf8_lossy(&data);
+
+    let image = match day {
+        8 => day08::parse(&text).map(|i| day08::render(&i)),
+        10 => day10::parse(&text).map(|i| day10::render(&i)),
+        12 => day12::parse(&text).map(|i| day12::render(&i)),
+        14 => day14::parse(&text).map(|i| day14::render(&i, &config(args, &source), args.part != Part::One)),
+        15 => day15::parse(&text).map(|i| day15::render(&i, &config(args, &source))),
+        _ => {
+            eprintln!("Day {} can't be rendered (only days 8, 10, 12, 14 and 15)", day);
+            process::exit(1);
+        },
+    };
+
+    let image = image.unwrap_or_else(|e| {
+        eprintln!("{}", e.with_file(&source));
+        process::exit(1);
+    });
+    if let Err(e) = image.save(path) {
+        eprintln!("Can't write {}: {}", path.display(), e);
+        process::exit(1);
+    }
+}
+
+// Part 1's simulation for `--part 1`, part 2's otherwise
+fn animate(args: &Args, speed: f64) {
+    let Some(day) = args.day else {
+        eprintln!("animate needs a --day (5, 9, 10 or 14)");
+        process::exit(1);
+    };
+
+    let source = input_source(args, day);
+    let data = read_source(&source).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let text = String::from_utf8_lossy(&data);
+
+    let part_1 = args.part == Part::One;
+    let res = match day {
+        5 => day05::parse(&text).map(|i| player::play(day05::steps(&i, part_1), speed)),
+        9 => day09::parse(&text).map(|i| player::play(day09::steps(&i, if part_1 { 2 } else { 10 }), speed)),
+        10 => day10::parse(&text).map(|i| player::play(day10::steps(&i, &config(args, &source)), speed)),
+        14 => day14::parse(&text).map(|i| player::play(day14::steps(&i, &config(args, &source), !part_1), speed)),
+        _ => {
+            eprintln!("Day {} has no animation (only days 5, 9, 10 and 14)", day);
+            process::exit(1);
+        },
+    };
+
+    match res {
+        Ok(Ok(())) => (),
+        Ok(Err(e)) => {
+            eprintln!("Can't animate in this terminal: {}", e);
+            process::exit(1);
+        },
+        Err(e) => {
+            eprintln!("{}", e.with_file(&source));
+            process::exit(1);
+        },
+    }
+}
+
+// A file that fails, even by panicking, gets its error recorded and the
+// rest still run
+fn batch(args: &Args, dir: &Path) {
+    let Some(day) = args.day else {
+        eprintln!("batch needs a --day");
+        process::exit(1);
+    };
+
+    let files = batch::inputs(dir).unwrap_or_else(|e| {
+        eprintln!("Can't read {}: {}", dir.display(), e);
+        process::exit(1);
+    });
+
+    let rows = files.into_iter()
+        .map(|file| {
+            let source = InputSource::File(file.clone());
+            let outcome = panic::catch_unwind(|| {
+                params(args, &source).and_then(|params| report(args, day, &source, &params))
+            })
+            .unwrap_or_else(|e| Err(format!("panicked: {}", panic_message(&*e))));
+            batch::Row { file, outcome }
+        })
+        .collect::<Vec<_>>();
+
+    print!("{}", batch::csv(&rows));
+}
+
+// Part 1's rules for `--part 1`, part 2's otherwise, as for `animate`
+fn repl(args: &Args) {
+    let Some(day) = args.day else {
+        eprintln!("repl needs a --day (7, 8, 9, 10, 11 or 15)");
+        process::exit(1);
+    };
+
+    let source = input_source(args, day);
+    let data = read_source(&source).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let text = String::from_utf8_lossy(&data);
+
+    let explore = |explorer: &mut dyn Explore| {
+        repl::run(explorer, &format!("day{:02}> ", day), io::stdin().lock(), io::stdout())
+    };
+    let part_1 = args.part == Part::One;
+    let res = match day {
+        7 => day07::parse(&text).map(|i| explore(&mut day07::Explorer::new(&i))),
+        8 => day08::parse(&text).map(|i| explore(&mut day08::Explorer::new(&i))),
+        9 => day09::parse(&text).map(|i| explore(&mut Stepper::new(day09::steps(&i, if part_1 { 2 } else { 10 })))),
+        10 => day10::parse(&text).map(|i| explore(&mut Stepper::new(day10::steps(&i, &config(args, &source))))),
+        11 => day11::parse(&text).map(|i| explore(&mut day11::Explorer::new(&i, &config(args, &source), !part_1))),
+        15 => day15::parse(&text).map(|i| explore(&mut day15::Explorer::new(&i, &config(args, &source)))),
+        _ => {
+            eprintln!("Day {} has no repl (only days 7, 8, 9, 10, 11 and 15)", day);
+            process::exit(1);
+        },
+    };
+
+    match res {
+        Ok(Ok(())) => (),
+        Ok(Err(e)) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        },
+        Err(e) => {
+            eprintln!("{}", e.with_file(&source));
+            process::exit(1);
+        },
+    }
+}
+
+fn generate(args: &Args, size: usize, seed: u64) {
+    let Some(day) = args.day else {
+        eprintln!("gen needs a --day (one of {:?})", gen::DAYS);
+        process::exit(1);
+    };
+
+    match gen::generate(day, size, seed) {
+        Some(input) => print!("{}", input),
+        None => {
+            eprintln!("Can't generate input for day {} (only for days {:?})", day, gen::DAYS);
+            process::exit(1);
+        },
+    }
+}
+
+fn new_day(args: &Args, regex: bool) {
+    let Some(day) = args.day else {
+        eprintln!("new needs a --day");
+        process::exit(1);
+    };
+
+    // The workspace this was built from
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let files = scaffold::new_day(root, day, regex).unwrap_or_else(|e| {
+        eprintln!("Can't create day {}: {}", day, e);
+        process::exit(1);
+    });
+
+    for f in files {
+        println!("Created {}", f.display());
+    }
+    println!("To run it from here, add day{:02} to aoc's dependencies and to DAYS", day);
+}
+
+fn main() {
+    let args = Args::parse();
+    common::init_tracing(args.verbose);
+
+    // Only `new` makes sense for days that aren't there yet
+    if let Some(day) = args.day.filter(|&d| d as usize > DAYS.len()) {
+        if !matches!(args.command, Some(Command::New { .. })) {
+            eprintln!("There's no day {} yet (only days 1 to {})", day, DAYS.len());
+            process::exit(1);
+        }
+    }
+
+    match &args.command {
+        None => {
+            if let Some(path) = &args.render {
+                render(&args, path);
+            }
+            run(&args);
+        },
+        Some(Command::RunAll { jobs }) => run_all(&args, *jobs),
+        Some(Command::Verify { record, answers }) => verify(&args, *record, answers),
+        Some(Command::Animate { speed }) => animate(&args, *speed),
+        Some(Command::Batch { dir }) => batch(&args, dir),
+        Some(Command::Repl) => repl(&args),
+        Some(Command::New { regex }) => new_day(&args, *regex),
+        Some(Command::Gen { size, seed }) => generate(&args, *size, *seed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn every_day_solves_its_example() {
+        for (day, entry) in (1..).zip(DAYS.iter()) {
+            // With the example's parameters, where it has any
+            let example = InputSource::from_arg(&entry.default_input.replace("input.txt", "example.txt"));
+            let params = Params::for_input(&example).unwrap();
+            let report = run_day(day, &example, &params, Part::All)
+                .unwrap_or_else(|e| panic!("day {}: {}", day, e));
+            assert_eq!(report.run.results.iter().map(|r| r.part).collect::<Vec<_>>(), [1, 2]);
+        }
+    }
+
+    #[test]
+    fn streaming_gives_the_same_answers() {
+        let answers = |r: &DayReport| r.run.results.iter().map(|p| p.answer.clone()).collect::<Vec<_>>();
+        let same = |day: u8, source: &InputSource, params: &Params| {
+            match (run_day(day, source, params, Part::All), stream_day(day, source, params, Part::All)) {
+                (Ok(whole), Ok(streamed)) => {
+                    assert_eq!(answers(&streamed), answers(&whole), "day {} on {}", day, source);
+                    assert_eq!(streamed.input_checksum, whole.input_checksum);
+                },
+                (Err(whole), Err(streamed)) => assert_eq!(streamed, whole, "day {} on {}", day, source),
+                (whole, streamed) => panic!("day {} on {}: {:?} but streamed {:?}", day, source, whole, streamed),
+            }
+        };
+
+        // Short and odd inputs, and ones that don't parse
+        let dir = env::temp_dir().join(format!("aoc-stream-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let odd = [
+            (1, vec!["1\n\n2\n", "\n\n\n", "5", "1\n\n\n2\n3\n\n4\n", "1\nx\n"]),
+            (6, vec!["abc", "abcd\r\nefgh\r\n", "ab\rcd", "aaaaaaaa\n", "abcdefghijklmn", "abC"]),
+            (7, vec![
+                "$ cd /\n$ cd ..\n$ ls\n10 a\n",
+                "$ ls\n5 b\n$ cd x\n$ ls\n7 c\n$ cd /\n$ cd y\n$ ls\n3 d\n$ cd ..\n$ cd ..\n",
+                "$ cd /\n$ ls\n69999999 a\n",
+                "$ cd /\n$ ls\n70000001 a\n",
+                "$ cd /\n$ ls\nbig a\n",
+                "$ ls\n5 b\n$ cd x\n7 c\n",
+                "$ cd /\n10 a\n",
+            ]),
+            (9, vec!["R 4\n", "U -3\nL 0\n", "R 1\nX 2\n"]),
+            (10, vec!["noop\naddx 3\naddx -5\n", "noop", "addx\n"]),
+        ];
+        for (day, inputs) in odd {
+            for (i, input) in inputs.into_iter().chain(["", "\n"]).enumerate() {
+                let path = dir.join(format!("day{:02}-{}.txt", day, i));
+                fs::write(&path, input).unwrap();
+                same(day, &InputSource::File(path), &Params::default());
+            }
+        }
+        fs::remove_dir_all(&dir).unwrap();
+
+        for day in [1, 6, 7, 9, 10] {
+            let input = DAYS[day as usize - 1].default_input;
+            same(day, &InputSource::from_arg(input), &Params::default());
+
+            // With the example's parameters, where it has any
+            let example = InputSource::from_arg(&input.replace("input.txt", "example.txt"));
+            same(day, &example, &Params::for_input(&example).unwrap());
+        }
+    }
+
+    #[test]
+    fn unreadable_input_isnt_the_end_of_it() {
+        let path = env::temp_dir().join(format!("aoc-unreadable-{}.txt", process::id()));
+        for (day, first) in [(1, "1000"), (7, "$ cd /"), (9, "R 4"), (10, "noop")] {
+            fs::write(&path, [first.as_bytes(), b"\n\xff\n", first.as_bytes()].concat()).unwrap();
+            let e = stream_day(day, &InputSource::File(path.clone()), &Params::default(), Part::All).unwrap_err();
+            assert!(e.ends_with(":2: can't read the input: stream did not contain valid UTF-8"), "day {}: {}", day, e);
+        }
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn overrides_reach_the_day() {
+        let example = InputSource::from_arg(&day15::DEFAULT_INPUT.replace("input.txt", "example.txt"));
+        let mut params = Params::for_input(&example).unwrap();
+        params.set("interesting_line=9").unwrap();
+        let report = run_day(15, &example, &params, Part::One).unwrap();
+        assert_eq!(report.run.results[0].answer, Answer::Int(25));
+
+        params.set("intersting_line=9").unwrap();
+        assert!(run_day(15, &example, &params, Part::One).is_err());
+    }
+
+    #[test]
+    fn bad_params_are_reported() {
+        for (day, assignment) in [
+            (6, "packet_window=0"), (7, "disk_size=1"), (10, "signal_cycles=[20, 0]"), (11, "relief=0"), (15, "max=-1"),
+        ] {
+            let mut params = Params::default();
+            params.set(assignment).unwrap();
+            let source = InputSource::from_arg(DAYS[day - 1].default_input);
+            let e = run_day(day as u8, &source, &params, Part::All).unwrap_err();
+            assert!(e.starts_with("Bad config: "), "day {}: {}", day, e);
+        }
+
+        // Fine as far as the config goes, but not for this input
+        let mut params = Params::default();
+        params.set("disk_size=100").unwrap();
+        params.set("needed_size=10").unwrap();
+        let source = InputSource::from_arg(day07::DEFAULT_INPUT);
+        let report = run_day(7, &source, &params, Part::Two).unwrap();
+        assert!(report.run.results[0].answer.to_string().starts_with("More used"));
+        let report = stream_day(7, &source, &params, Part::Two).unwrap();
+        assert!(report.run.results[0].answer.to_string().starts_with("More used"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::{cursor, execute, queue, style::Print, terminal};
+use common::anim::{Canvas, Frame, Viewport};
+
+// How often the screen is redrawn (at most)
+const TICK: Duration = Duration::from_millis(40);
+
+// Lines under the picture: the caption and the status line
+const FOOTER: u16 = 2;
+
+const HELP: &str = "space: pause  .: step  +/-: speed  q: quit";
+
+/// Play `frames` in the terminal, starting at `speed` frames a second. Space
+/// pauses, `.` (or right) steps a frame at a time while paused, `+` and `-`
+/// double or halve the speed and `q` quits.
+pub fn play(frames: impl Iterator<Item = Frame>, speed: f64) -> io::Result<()> {
+    let _terminal = Terminal::enter()?;
+    let (cols, rows) = terminal::size()?;
+    let mut player = Player::new(frames, cols, rows);
+
+    let mut speed = speed;
+    let mut paused = false;
+    let mut due = 0.0;
+    let mut last = Instant::now();
+
+    player.advance(1);
+    loop {
+        // Catch up with however many frames are due since the last tick
+        let now = Instant::now();
+        if !paused {
+            due += speed * (now - last).as_secs_f64();
+            player.advance(due as usize);
+            due = due.fract();
+        }
+        last = now;
+
+        player.draw(&mut io::stdout(), paused, speed)?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+                KeyCode::Char(' ') => paused = !paused,
+                KeyCode::Char('.') | KeyCode::Right if paused => player.advance(1),
+                KeyCode::Char('+') | KeyCode::Char('=') => speed *= 2.0,
+                KeyCode::Char('-') => speed = (speed / 2.0).max(0.25),
+                _ => (),
+            },
+            Event::Resize(cols, rows) => player.resize(cols, rows),
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+struct Player<I> {
+    frames: I,
+    canvas: Canvas,
+    view: Option<Viewport>,
+    size: (usize, usize),
+    caption: String,
+    count: usize,
+    finished: bool,
+}
+
+impl<I: Iterator<Item = Frame>> Player<I> {
+    fn new(frames: I, cols: u16, rows: u16) -> Player<I> {
+        Player {
+            frames,
+            canvas: Canvas::new(),
+            view: None,
+            size: (cols as usize, rows.saturating_sub(FOOTER) as usize),
+            caption: String::new(),
+            count: 0,
+            finished: false,
+        }
+    }
+
+    fn advance(&mut self, n: usize) {
+        let mut focus = None;
+        for _ in 0..n {
+            match self.frames.next() {
+                Some(frame) => {
+                    self.canvas.apply(&frame);
+                    self.count += 1;
+                    focus = Some(frame.focus);
+                    self.caption = frame.caption;
+                },
+                None => {
+                    self.finished = true;
+                    break;
+                },
+            }
+        }
+
+        // The viewport starts off centred on the action, and follows it
+        if let Some(focus) = focus {
+            let (width, height) = self.size;
+            self.view.get_or_insert_with(|| Viewport::centred(focus, width, height))
+                .follow(focus);
+        }
+    }
+
+    fn resize(&mut self, cols: u16, rows: u16) {
+        self.size = (cols as usize, rows.saturating_sub(FOOTER) as usize);
+        if let Some(view) = self.view.as_mut() {
+            view.width = self.size.0;
+            view.height = self.size.1;
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write, paused: bool, speed: f64) -> io::Result<()> {
+        let Some(view) = self.view else {
+            return Ok(());
+        };
+
+        let state = match (self.finished, paused) {
+            (true, _) => "finished",
+            (false, true) => "paused",
+            (false, false) => "playing",
+        };
+        let status = format!("frame {} ({}, {} frames/s)  {}", self.count, state, speed, HELP);
+
+        let picture = self.canvas.render(&view);
+        let lines = picture.lines().chain([self.caption.as_str(), status.as_str()]);
+        for (row, line) in lines.enumerate() {
+            let line = line.chars().take(self.size.0).collect::<String>();
+            queue!(
+                out,
+                cursor::MoveTo(0, row as u16),
+                Print(line),
+                terminal::Clear(terminal::ClearType::UntilNewLine),
+            )?;
+        }
+
+        out.flush()
+    }
+}
+
+// Raw mode on an alternate screen, for as long as it's around
+struct Terminal;
+
+impl Terminal {
+    fn enter() -> io::Result<Terminal> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Terminal)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
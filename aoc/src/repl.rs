@@ -0,0 +1,72 @@
+use std::io::{self, BufRead, Write};
+use common::explore::Explore;
+
+const BUILTINS: [(&str, &str); 2] = [
+    ("help", "this list"),
+    ("quit", "leave (so does end of input)"),
+];
+
+/// Read commands from `input` a line at a time, run them against `explorer`
+/// and write what they say to `out`. A command that fails says why and
+/// leaves the state as it was.
+pub fn run(explorer: &mut dyn Explore, prompt: &str, input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+    writeln!(out, "Type help for the commands")?;
+    write!(out, "{}", prompt)?;
+    out.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+        let words = line.split_whitespace().collect::<Vec<_>>();
+        match words.first() {
+            None => (),
+            Some(&"quit") | Some(&"exit") => return Ok(()),
+            Some(&"help") => {
+                let help = explorer.help().into_iter().chain(BUILTINS).collect::<Vec<_>>();
+                let width = help.iter().map(|(usage, _)| usage.len()).max().unwrap();
+                for (usage, what) in help {
+                    writeln!(out, "{:<width$}  {}", usage, what, width = width)?;
+                }
+            },
+            Some(_) => match explorer.command(&words) {
+                Ok(result) => writeln!(out, "{}", result)?,
+                Err(e) => writeln!(out, "{}", e)?,
+            },
+        }
+
+        write!(out, "{}", prompt)?;
+        out.flush()?;
+    }
+
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session() {
+        let input = day07::parse(include_str!("../../day07/src/example.txt")).unwrap();
+        let mut explorer = day07::Explorer::new(&input);
+        let commands = "tree /d\n\ndu /a\nls\nhelp\nquit\ntree\n";
+        let mut out = Vec::new();
+        run(&mut explorer, "> ", commands.as_bytes(), &mut out).unwrap();
+
+        let expected = "\
+Type help for the commands
+> - d (dir, size=24933642)
+  - j (file, size=4060174)
+  - d.log (file, size=8033020)
+  - d.ext (file, size=5626152)
+  - k (file, size=7214296)
+> > 584\t/a/e
+94853\t/a
+> Unknown command \"ls\"
+> tree [PATH]  what's in a directory (/ if left out), with sizes
+du [PATH]    total size of a directory and each one in it
+help         this list
+quit         leave (so does end of input)
+> ";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt::Write;
+use std::time::Duration;
+use common::Run;
+
+/// What running one day produced, and what it cost
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub input_checksum: String,
+    pub run: Run,
+    /// Most heap in use at once while parsing and solving, in bytes
+    pub peak_heap: usize,
+}
+
+fn ms(d: Duration) -> String {
+    format!("{:.3} ms", d.as_secs_f64() * 1000.0)
+}
+
+fn bytes(n: usize) -> String {
+    match n {
+        n if n >= 1 << 20 => format!("{:.1} MiB", n as f64 / (1 << 20) as f64),
+        n if n >= 1 << 10 => format!("{:.1} KiB", n as f64 / (1 << 10) as f64),
+        n => format!("{} B", n),
+    }
+}
+
+/// A table of timings and peak memory, one row per day plus a total
+pub fn summary(reports: &[DayReport]) -> String {
+    let mut out = String::new();
+    writeln!(out, "{:<5} {:>12} {:>12} {:>12} {:>12} {:>11}", "Day", "Parse", "Part 1", "Part 2", "Total", "Peak heap").unwrap();
+
+    let part_time = |r: &DayReport, part: u8| {
+        r.run.results.iter()
+            .find(|p| p.part == part)
+            .map(|p| p.elapsed)
+    };
+
+    let mut totals = [Duration::ZERO; 4];
+    let mut max_heap = 0;
+    for r in reports {
+        let times = [Some(r.run.parse_time), part_time(r, 1), part_time(r, 2)];
+        let total = times.iter().flatten().sum::<Duration>();
+        for (t, time) in totals.iter_mut().zip(times.iter().copied().chain([Some(total)])) {
+            *t += time.unwrap_or_default();
+        }
+        max_heap = max_heap.max(r.peak_heap);
+
+        let [parse, p1, p2] = times.map(|t| t.map_or("-".to_string(), ms));
+        writeln!(out, "{:<5} {:>12} {:>12} {:>12} {:>12} {:>11}",
+                 format!("{:02}", r.day), parse, p1, p2, ms(total), bytes(r.peak_heap)).unwrap();
+    }
+
+    let [parse, p1, p2, total] = totals.map(ms);
+    writeln!(out, "{:<5} {:>12} {:>12} {:>12} {:>12} {:>11}", "All", parse, p1, p2, total, bytes(max_heap)).unwrap();
+    out
+}
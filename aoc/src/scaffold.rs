@@ -0,0 +1,135 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs.tmpl");
+const MAIN_RS: &str = include_str!("../templates/main.rs.tmpl");
+
+const REGEX_DEPS: &str = "regex = \"1\"\nlazy_static = \"1.4.0\"\n";
+
+fn fill(template: &str, day: u8, regex: bool) -> String {
+    template
+        .replace("{{day}}", &format!("{:02}", day))
+        .replace("{{deps}}", if regex { REGEX_DEPS } else { "" })
+}
+
+/// A new crate for `day` under `root`, laid out like the others: a library
+/// with `read_input`, `part_1` and `part_2` still to be written, a thin
+/// binary, empty example and input files and a failing test for the
+/// example. It's added to `root`'s workspace if there is one. Returns the
+/// files created.
+pub fn new_day(root: &Path, day: u8, regex: bool) -> io::Result<Vec<PathBuf>> {
+    let name = format!("day{:02}", day);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", dir.display())));
+    }
+
+    let files = [
+        ("Cargo.toml", fill(CARGO_TOML, day, regex)),
+        ("src/lib.rs", fill(LIB_RS, day, regex)),
+        ("src/main.rs", fill(MAIN_RS, day, regex)),
+        ("src/example.txt", String::new()),
+        ("src/input.txt", String::new()),
+    ];
+
+    fs::create_dir_all(dir.join("src"))?;
+    let mut created = Vec::new();
+    for (file, contents) in files {
+        let path = dir.join(file);
+        fs::write(&path, contents)?;
+        created.push(path);
+    }
+
+    let manifest = root.join("Cargo.toml");
+    if manifest.is_file() {
+        let text = fs::read_to_string(&manifest)?;
+        if let Some(text) = add_member(&text, &name).map_err(io::Error::other)? {
+            fs::write(&manifest, text)?;
+        }
+    }
+
+    Ok(created)
+}
+
+// The manifest with `name` added to its workspace's members, keeping its
+// layout, or None if it isn't a workspace or already has it
+fn add_member(manifest: &str, name: &str) -> Result<Option<String>, String> {
+    let table = manifest.parse::<toml::Table>().map_err(|e| e.to_string())?;
+    let Some(workspace) = table.get("workspace") else {
+        return Ok(None);
+    };
+
+    let members = workspace.get("members").and_then(|m| m.as_array());
+    if members.is_some_and(|m| m.iter().any(|m| m.as_str() == Some(name))) {
+        return Ok(None);
+    }
+
+    // Onto the end of the members list, or a new one
+    let entry = format!("    \"{}\",\n", name);
+    let mut out = manifest.to_string();
+    match manifest.find("members = [") {
+        Some(start) => {
+            let end = start + manifest[start..].find(']').ok_or("Unterminated members list")?;
+            let line_start = manifest[..end].rfind('\n').map_or(0, |i| i + 1);
+            if manifest[line_start..end].trim().is_empty() {
+                out.insert_str(line_start, &entry);
+            } else {
+                // All on one line
+                out.insert_str(end, &format!(", \"{}\"", name));
+            }
+        },
+        None => {
+            let start = manifest.find("[workspace]").ok_or("No [workspace] table")?;
+            let line_end = start + manifest[start..].find('\n').map_or(manifest.len() - start, |i| i + 1);
+            out.insert_str(line_end, &format!("members = [\n{}]\n", entry));
+        },
+    }
+
+    Ok(Some(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    #[test]
+    fn members() {
+        let manifest = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"day15\",\n]\n";
+        assert_eq!(
+            add_member(manifest, "day16").unwrap().unwrap(),
+            "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"day15\",\n    \"day16\",\n]\n",
+        );
+        assert_eq!(add_member(manifest, "day15"), Ok(None));
+
+        assert_eq!(add_member("[workspace]\nmembers = [\"aoc\"]\n", "day16").unwrap().unwrap(),
+                   "[workspace]\nmembers = [\"aoc\", \"day16\"]\n");
+        assert_eq!(add_member("[workspace]\n", "day16").unwrap().unwrap(),
+                   "[workspace]\nmembers = [\n    \"day16\",\n]\n");
+        assert_eq!(add_member("[package]\nname = \"x\"\n", "day16"), Ok(None));
+    }
+
+    #[test]
+    fn new_crate() {
+        let root = env::temp_dir().join(format!("aoc-new-{}", process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = [\n    \"day01\",\n]\n").unwrap();
+
+        let files = new_day(&root, 16, true).unwrap();
+        assert_eq!(files.len(), 5);
+        let lib = fs::read_to_string(root.join("day16/src/lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day16 {"));
+        assert!(!lib.contains("{{"));
+        assert!(lib.contains("parse::lines(reader)") && !lib.contains("todo!()"));
+        let cargo = fs::read_to_string(root.join("day16/Cargo.toml")).unwrap();
+        assert!(cargo.contains("name = \"day16\"") && cargo.contains("regex"));
+        assert!(fs::read_to_string(root.join("Cargo.toml")).unwrap().contains("\"day16\""));
+        assert_eq!(fs::read_to_string(root.join("day16/src/input.txt")).unwrap(), "");
+
+        assert_eq!(new_day(&root, 16, false).unwrap_err().kind(), io::ErrorKind::AlreadyExists);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
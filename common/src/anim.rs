@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+use crate::geometry::Point2;
+
+type Point = Point2<isize>;
+
+/// One step of a simulation: the cells it changed since the previous step,
+/// where the action is and a line saying what happened. A blank (`' '`)
+/// clears a cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub changes: Vec<(Point, char)>,
+    pub focus: Point,
+    pub caption: String,
+}
+
+impl Frame {
+    pub fn new(focus: Point, caption: impl Into<String>) -> Frame {
+        Frame { changes: Vec::new(), focus, caption: caption.into() }
+    }
+
+    pub fn set(&mut self, p: Point, c: char) {
+        self.changes.push((p, c));
+    }
+
+    /// Write `s` left to right, starting at `p`
+    pub fn text(&mut self, p: Point, s: &str) {
+        for (i, c) in s.chars().enumerate() {
+            self.set(Point2::new(p.x + i as isize, p.y), c);
+        }
+    }
+}
+
+/// Everything the frames so far have drawn
+#[derive(Debug, Clone, Default)]
+pub struct Canvas {
+    cells: HashMap<Point, char>,
+}
+
+impl Canvas {
+    pub fn new() -> Canvas {
+        Canvas::default()
+    }
+
+    pub fn apply(&mut self, frame: &Frame) {
+        for &(p, c) in frame.changes.iter() {
+            if c == ' ' {
+                self.cells.remove(&p);
+            } else {
+                self.cells.insert(p, c);
+            }
+        }
+    }
+
+    pub fn get(&self, p: Point) -> char {
+        self.cells.get(&p).copied().unwrap_or(' ')
+    }
+
+    /// The part of the canvas in view, one line per row
+    pub fn render(&self, view: &Viewport) -> String {
+        (0..view.height as isize)
+            .map(|y| (0..view.width as isize)
+                .map(|x| self.get(Point2::new(view.origin.x + x, view.origin.y + y)))
+                .collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The part of a canvas on screen, `origin` being its top left corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Viewport {
+    pub origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Viewport {
+    pub fn centred(p: Point, width: usize, height: usize) -> Viewport {
+        let origin = Point2::new(p.x - width as isize / 2, p.y - height as isize / 2);
+        Viewport { origin, width, height }
+    }
+
+    /// Scroll, as little as possible, to keep `p` a quarter of the view away
+    /// from the edges
+    pub fn follow(&mut self, p: Point) {
+        self.origin.x = follow_axis(self.origin.x, self.width, p.x);
+        self.origin.y = follow_axis(self.origin.y, self.height, p.y);
+    }
+}
+
+fn follow_axis(origin: isize, size: usize, p: isize) -> isize {
+    let margin = size as isize / 4;
+    let (low, high) = (origin + margin, origin + size as isize - 1 - margin);
+    if p < low {
+        origin - (low - p)
+    } else if p > high {
+        origin + (p - high)
+    } else {
+        origin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canvas_applies_changes() {
+        let mut canvas = Canvas::new();
+        let mut frame = Frame::new(Point2::new(0, 0), "");
+        frame.text(Point2::new(-1, 0), "[A]");
+        frame.set(Point2::new(0, 1), '#');
+        canvas.apply(&frame);
+
+        let mut frame = Frame::new(Point2::new(0, 0), "");
+        frame.set(Point2::new(0, 1), ' ');
+        canvas.apply(&frame);
+
+        let view = Viewport { origin: Point2::new(-2, 0), width: 5, height: 2 };
+        assert_eq!(canvas.render(&view), " [A] \n     ");
+    }
+
+    #[test]
+    fn viewport_follows() {
+        let mut view = Viewport::centred(Point2::new(0, 0), 8, 4);
+        assert_eq!(view.origin, Point2::new(-4, -2));
+
+        // Within the margins, nothing moves
+        view.follow(Point2::new(1, 0));
+        assert_eq!(view.origin, Point2::new(-4, -2));
+
+        view.follow(Point2::new(5, -4));
+        assert_eq!(view.origin, Point2::new(0, -5));
+    }
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+use serde::{Serialize, Serializer};
+use crate::PartResult;
+
+/// The result of solving one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    /// A multi-line rendering, e.g. letters drawn on a screen. Rows are
+    /// separated by `\n`.
+    Image(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::Image(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// Numbers stay numbers; strings and images become JSON strings
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Int(x) => serializer.serialize_i64(*x),
+            Answer::Str(s) | Answer::Image(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(x: i64) -> Answer {
+        Answer::Int(x)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(x: isize) -> Answer {
+        Answer::Int(x as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(x: usize) -> Answer {
+        Answer::Int(x as i64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// Print each part's answer, starting images on their own line
+pub fn print_answers(results: &[PartResult]) {
+    for r in results {
+        match r.answer {
+            Answer::Image(_) => println!("Part {}:\n{}", r.part, r.answer),
+            _ => println!("Part {}: {}", r.part, r.answer),
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use crate::InputSource;
+
+/// A day's puzzle parameters as a config file gives them, with any overrides
+/// from the command line on top. Whatever's left out keeps the value the real
+/// puzzle uses.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params(toml::Table);
+
+impl Params {
+    pub fn parse(text: &str) -> Result<Params, ConfigError> {
+        text.parse().map(Params).map_err(|e| ConfigError(e.to_string()))
+    }
+
+    pub fn load(path: &Path) -> Result<Params, ConfigError> {
+        fs::read_to_string(path)
+            .map_err(|e| ConfigError(e.to_string()))
+            .and_then(|text| Params::parse(&text))
+            .map_err(|e| ConfigError(format!("{}: {}", path.display(), e.0)))
+    }
+
+    /// The config file that goes with an input file, named like it but
+    /// ending in `.toml` (`example.toml` for `example.txt`). No file, or
+    /// reading from stdin, means no parameters.
+    pub fn for_input(source: &InputSource) -> Result<Params, ConfigError> {
+        match source {
+            InputSource::File(path) if path.with_extension("toml").is_file() => {
+                Params::load(&path.with_extension("toml"))
+            },
+            _ => Ok(Params::default()),
+        }
+    }
+
+    /// Override a parameter with `key=value`, the value being written as in
+    /// TOML (`source=[500, 0]`)
+    pub fn set(&mut self, assignment: &str) -> Result<(), ConfigError> {
+        let Some((key, value)) = assignment.split_once('=') else {
+            return Err(ConfigError(format!("Expected key=value, found {:?}", assignment)));
+        };
+
+        let mut table = Params::parse(&format!("value = {}", value))?.0;
+        self.0.insert(key.trim().to_string(), table.remove("value").unwrap());
+        Ok(())
+    }
+
+    /// The parameters on one line, in key order (`max = 20, source = [1, 2]`),
+    /// or nothing if the real puzzle's are all kept
+    pub fn fingerprint(&self) -> String {
+        self.0.iter()
+            .map(|(key, value)| format!("{} = {}", key, value))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// The parameters as a day's config type, which says what it expects
+    /// and checks the values make sense
+    pub fn get<C: DeserializeOwned + Validate>(&self) -> Result<C, ConfigError> {
+        let config: C = self.0.clone().try_into().map_err(|e: toml::de::Error| ConfigError(e.message().to_string()))?;
+        config.validate().map_err(ConfigError)?;
+        Ok(config)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError(String);
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bad config: {}", self.0)
+    }
+}
+
+impl Error for ConfigError {}
+
+/// What a day's config needs of its values beyond their types (a window
+/// that's at least one character, say), checked once they're read so a bad
+/// one is reported rather than panicking the solver
+pub trait Validate {
+    fn validate(&self) -> Result<(), String>;
+}
+
+/// The config of a day that doesn't have any parameters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoConfig {}
+
+impl Validate for NoConfig {
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(default, deny_unknown_fields)]
+    struct Config {
+        line: isize,
+        source: (isize, isize),
+    }
+
+    impl Default for Config {
+        fn default() -> Config {
+            Config { line: 2000000, source: (500, 0) }
+        }
+    }
+
+    impl Validate for Config {
+        fn validate(&self) -> Result<(), String> {
+            match self.line {
+                0.. => Ok(()),
+                _ => Err(format!("line must be at least 0, not {}", self.line)),
+            }
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        let mut params = Params::parse("line = 10\n").unwrap();
+        assert_eq!(params.get::<Config>().unwrap(), Config { line: 10, source: (500, 0) });
+
+        params.set("source = [1, 2]").unwrap();
+        params.set("line=20").unwrap();
+        assert_eq!(params.get::<Config>().unwrap(), Config { line: 20, source: (1, 2) });
+        assert_eq!(params.fingerprint(), "line = 20, source = [1, 2]");
+        assert_eq!(Params::default().fingerprint(), "");
+    }
+
+    #[test]
+    fn mistakes() {
+        assert!(Params::parse("lnie = 10").unwrap().get::<Config>().is_err());
+        assert!(Params::parse("line = \"ten\"").unwrap().get::<Config>().is_err());
+        assert!(Params::default().set("line").is_err());
+        assert!(Params::parse("line = 1").unwrap().get::<NoConfig>().is_err());
+        assert_eq!(Params::parse("line = -1").unwrap().get::<Config>().unwrap_err().to_string(),
+                   "Bad config: line must be at least 0, not -1");
+        assert_eq!(Params::default().get::<NoConfig>(), Ok(NoConfig {}));
+    }
+}
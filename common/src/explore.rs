@@ -0,0 +1,104 @@
+use std::str::FromStr;
+use crate::anim::{Canvas, Frame, Viewport};
+
+/// A day's puzzle state, for `aoc repl` to poke at a command at a time
+pub trait Explore {
+    /// How each command is used, and what it does
+    fn help(&self) -> Vec<(&'static str, &'static str)>;
+
+    /// Run a command, already split into words, returning what to print
+    fn command(&mut self, words: &[&str]) -> Result<String, String>;
+}
+
+/// The `i`th word of a command, or `default` if it's missing
+pub fn arg<T: FromStr>(words: &[&str], i: usize, default: Option<T>) -> Result<T, String> {
+    match (words.get(i), default) {
+        (Some(w), _) => w.parse().map_err(|_| format!("Can't make sense of {:?}", w)),
+        (None, Some(d)) => Ok(d),
+        (None, None) => Err(format!("{} needs more arguments", words[0])),
+    }
+}
+
+// What `show` draws around the action
+const SHOW_WIDTH: usize = 60;
+const SHOW_HEIGHT: usize = 20;
+
+/// Steps through a simulation's frames, the same ones `aoc animate` plays
+pub struct Stepper<I> {
+    frames: I,
+    canvas: Canvas,
+    last: Option<Frame>,
+    count: usize,
+}
+
+impl<I: Iterator<Item = Frame>> Stepper<I> {
+    pub fn new(frames: I) -> Stepper<I> {
+        Stepper { frames, canvas: Canvas::new(), last: None, count: 0 }
+    }
+
+    fn step(&mut self, n: usize) -> String {
+        for _ in 0..n {
+            let Some(frame) = self.frames.next() else {
+                return format!("Finished after {} steps", self.count);
+            };
+            self.canvas.apply(&frame);
+            self.count += 1;
+            self.last = Some(frame);
+        }
+
+        self.caption()
+    }
+
+    fn caption(&self) -> String {
+        self.last.as_ref().map_or("Not started".to_string(), |f| format!("{}: {}", self.count, f.caption))
+    }
+}
+
+impl<I: Iterator<Item = Frame>> Explore for Stepper<I> {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("step [N]", "go N steps on (1 if left out)"),
+            ("show", "draw what's around the action"),
+        ]
+    }
+
+    fn command(&mut self, words: &[&str]) -> Result<String, String> {
+        match words[0] {
+            "step" => Ok(self.step(arg(words, 1, Some(1))?)),
+            "show" => {
+                let focus = self.last.as_ref().map(|f| f.focus).unwrap_or_default();
+                let view = Viewport::centred(focus, SHOW_WIDTH, SHOW_HEIGHT);
+                let picture = self.canvas.render(&view);
+                let lines = picture.lines()
+                    .map(str::trim_end)
+                    .skip_while(|l| l.is_empty())
+                    .collect::<Vec<_>>();
+                Ok(format!("{}\n{}", lines.join("\n").trim_end(), self.caption()))
+            },
+            _ => Err(format!("Unknown command {:?}", words[0])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Point2;
+
+    #[test]
+    fn stepper() {
+        let frames = (0..3).map(|i| {
+            let mut f = Frame::new(Point2::new(i, 0), format!("at {}", i));
+            f.set(Point2::new(i, 0), '#');
+            f
+        });
+        let mut stepper = Stepper::new(frames);
+
+        assert_eq!(stepper.command(&["step"]), Ok("1: at 0".to_string()));
+        assert_eq!(stepper.command(&["step", "2"]), Ok("3: at 2".to_string()));
+        assert_eq!(stepper.command(&["step", "5"]), Ok("Finished after 3 steps".to_string()));
+        assert!(stepper.command(&["show"]).unwrap().contains("###"));
+        assert!(stepper.command(&["step", "x"]).is_err());
+        assert!(stepper.command(&["jump"]).is_err());
+    }
+}
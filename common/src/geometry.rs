@@ -0,0 +1,253 @@
+use std::ops::{Add, AddAssign, Sub};
+
+/// Coordinate types a [`Point2`] can be made of.
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    /// `self + d`, or `None` if that doesn't fit in the type
+    fn offset(self, d: isize) -> Option<Self>;
+}
+
+impl Coord for isize {
+    fn offset(self, d: isize) -> Option<isize> {
+        self.checked_add(d)
+    }
+}
+
+impl Coord for usize {
+    fn offset(self, d: isize) -> Option<usize> {
+        self.checked_add_signed(d)
+    }
+}
+
+/// A position on a 2D plane. `y` grows downwards, like rows on a screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+
+    /// The point `v` away from this one, if it can be represented
+    pub fn offset(&self, v: Vec2) -> Option<Point2<T>> {
+        Some(Point2::new(self.x.offset(v.x)?, self.y.offset(v.y)?))
+    }
+
+    pub fn manhattan(&self, other: &Point2<T>) -> T {
+        let (dx, dy) = self.abs_diff(other);
+        dx + dy
+    }
+
+    pub fn chebyshev(&self, other: &Point2<T>) -> T {
+        let (dx, dy) = self.abs_diff(other);
+        dx.max(dy)
+    }
+
+    /// Neighbours up, right, down and left (those that can be represented)
+    pub fn neighbours4(self) -> impl Iterator<Item = Point2<T>> {
+        Dir4::ALL.iter().filter_map(move |d| self.offset(d.vec()))
+    }
+
+    /// Neighbours in all 8 directions, diagonals included
+    pub fn neighbours8(self) -> impl Iterator<Item = Point2<T>> {
+        Dir8::ALL.iter().filter_map(move |d| self.offset(d.vec()))
+    }
+
+    fn abs_diff(&self, other: &Point2<T>) -> (T, T) {
+        let dx = self.x.max(other.x) - self.x.min(other.x);
+        let dy = self.y.max(other.y) - self.y.min(other.y);
+        (dx, dy)
+    }
+}
+
+impl Add<Vec2> for Point2<isize> {
+    type Output = Point2<isize>;
+
+    fn add(self, v: Vec2) -> Point2<isize> {
+        Point2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point2<isize> {
+    fn add_assign(&mut self, v: Vec2) {
+        *self = *self + v;
+    }
+}
+
+impl Sub for Point2<isize> {
+    type Output = Vec2;
+
+    fn sub(self, other: Point2<isize>) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// A displacement between two points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2 { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    /// One step (at most) along each axis in the same direction
+    pub fn signum(&self) -> Vec2 {
+        Vec2::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn manhattan(&self) -> isize {
+        self.x.abs() + self.y.abs()
+    }
+
+    pub fn chebyshev(&self) -> isize {
+        self.x.abs().max(self.y.abs())
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn vec(&self) -> Vec2 {
+        match self {
+            Dir4::Up => Vec2::new(0, -1),
+            Dir4::Right => Vec2::new(1, 0),
+            Dir4::Down => Vec2::new(0, 1),
+            Dir4::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up, Dir8::UpRight, Dir8::Right, Dir8::DownRight,
+        Dir8::Down, Dir8::DownLeft, Dir8::Left, Dir8::UpLeft,
+    ];
+
+    pub fn vec(&self) -> Vec2 {
+        match self {
+            Dir8::Up => Vec2::new(0, -1),
+            Dir8::UpRight => Vec2::new(1, -1),
+            Dir8::Right => Vec2::new(1, 0),
+            Dir8::DownRight => Vec2::new(1, 1),
+            Dir8::Down => Vec2::new(0, 1),
+            Dir8::DownLeft => Vec2::new(-1, 1),
+            Dir8::Left => Vec2::new(-1, 0),
+            Dir8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+/// An axis-aligned rectangle, inclusive of both corners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coord> Bounds<T> {
+    pub fn new(min: Point2<T>, max: Point2<T>) -> Bounds<T> {
+        Bounds { min, max }
+    }
+
+    /// Smallest bounds containing all of `points`, `None` if there are none
+    pub fn from_points<'a>(points: impl IntoIterator<Item = &'a Point2<T>>) -> Option<Bounds<T>>
+    where
+        T: 'a,
+    {
+        points.into_iter().fold(None, |acc, p| match acc {
+            None => Some(Bounds::new(*p, *p)),
+            Some(b) => Some(Bounds::new(
+                Point2::new(b.min.x.min(p.x), b.min.y.min(p.y)),
+                Point2::new(b.max.x.max(p.x), b.max.y.max(p.y)),
+            )),
+        })
+    }
+
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+}
+
+impl Bounds<usize> {
+    /// `width` x `height` cells starting at the origin. Both must be non-zero.
+    pub fn from_size(width: usize, height: usize) -> Bounds<usize> {
+        Bounds::new(Point2::new(0, 0), Point2::new(width - 1, height - 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(2isize, 18);
+        let b = Point2::new(-2, 15);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!((a - b).manhattan(), 7);
+        assert_eq!((b - a).chebyshev(), 4);
+
+        let c = Point2::new(1usize, 5);
+        assert_eq!(c.manhattan(&Point2::new(4, 2)), 6);
+    }
+
+    #[test]
+    fn neighbours_stop_at_the_edge() {
+        let corner = Point2::new(0usize, 0);
+        assert_eq!(corner.neighbours4().count(), 2);
+        assert_eq!(corner.neighbours8().count(), 3);
+
+        let p = Point2::new(0isize, 0);
+        assert_eq!(p.neighbours4().count(), 4);
+        assert_eq!(p.neighbours8().count(), 8);
+    }
+
+    #[test]
+    fn bounds() {
+        let points = [Point2::new(3isize, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+        let b = Bounds::from_points(&points).unwrap();
+        assert_eq!(b, Bounds::new(Point2::new(-2, -1), Point2::new(3, 4)));
+        assert!(b.contains(&Point2::new(3, 4)));
+        assert!(!b.contains(&Point2::new(4, 0)));
+        assert_eq!(Bounds::<isize>::from_points(&[]), None);
+
+        assert!(!Bounds::from_size(5, 3).contains(&Point2::new(0, 3)));
+    }
+}
@@ -0,0 +1,207 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+use crate::geometry::{Point2, Vec2};
+use crate::ParseError;
+
+type Point = Point2<usize>;
+
+/// A rectangular block of cells, stored row by row. `(0, 0)` is the top left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Cells given row by row. Panics unless they make up whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(width > 0 && cells.len().is_multiple_of(width), "{} cells don't make rows of {}", cells.len(), width);
+        let height = cells.len() / width;
+        Grid { cells, width, height }
+    }
+
+    /// Build a grid from lines of text, one cell per character. `cell`
+    /// converts a character found at a (1-based) column; lines must all be
+    /// the same, non-zero, length.
+    pub fn parse<S, F>(lines: impl IntoIterator<Item = S>, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        S: AsRef<str>,
+        F: FnMut(char, usize) -> Result<T, ParseError>,
+    {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        for (i, l) in lines.into_iter().enumerate() {
+            let l = l.as_ref();
+            if i == 0 {
+                width = l.chars().count();
+                if width == 0 {
+                    return Err(ParseError::new(1, "a row of cells", "").at_line(1));
+                }
+            } else if l.chars().count() != width {
+                return Err(ParseError::new(1, format!("a row of {} cells", width), l).at_line(i + 1));
+            }
+
+            for (j, c) in l.chars().enumerate() {
+                cells.push(cell(c, j + 1).map_err(|e| e.at_line(i + 1))?);
+            }
+        }
+
+        if cells.is_empty() {
+            return Err(ParseError::new(1, "a row of cells", "").at_line(1));
+        }
+
+        Ok(Grid::from_vec(width, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.cells[p.x + p.y * self.width])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.cells[p.x + p.y * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// All cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Coordinates of all cells, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point2::new(x, y)))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn col(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// Coordinates walking from `from` (exclusive) in steps of `dir`, up to
+    /// the edge of the grid
+    pub fn ray(&self, from: Point, dir: Vec2) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        iter::successors(from.offset(dir), move |p| p.offset(dir))
+            .take_while(move |p| p.x < width && p.y < height)
+    }
+
+    /// Coordinates of the (up to 4) cells next to `p`
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        p.neighbours4()
+            .filter(move |n| n.x < width && n.y < height)
+    }
+
+    /// Coordinates of the (up to 8) cells around `p`, diagonals included
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        p.neighbours8()
+            .filter(move |n| n.x < width && n.y < height)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", p, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p).unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", p, width, height))
+    }
+}
+
+// One line per row, no trailing newline
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Dir4;
+
+    fn digits(s: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(s.lines(), |c, column| {
+            c.to_digit(10).ok_or_else(|| ParseError::new(column, "a digit", c.to_string()))
+        })
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Point2::new(2, 1)], 6);
+        assert_eq!(grid.get(Point2::new(3, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+
+        let err = digits("123\n4x6").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = digits("123\n45").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert!(digits("").is_err());
+    }
+
+    #[test]
+    fn iterators() {
+        let grid = digits("123\n456\n789").unwrap();
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [4, 5, 6]);
+        assert_eq!(grid.col(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+        assert_eq!(grid.ray(Point2::new(2, 2), Dir4::Up.vec()).map(|p| grid[p]).collect::<Vec<_>>(), [6, 3]);
+        assert_eq!(grid.ray(Point2::new(0, 1), Dir4::Left.vec()).count(), 0);
+        assert_eq!(grid.neighbours4(Point2::new(0, 0)).count(), 2);
+        assert_eq!(grid.neighbours8(Point2::new(1, 1)).count(), 8);
+    }
+}
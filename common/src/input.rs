@@ -0,0 +1,115 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+/// Where a solver reads its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// `-` means stdin, anything else is a file path
+    pub fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    /// Input named by the first command line argument, or `default` when
+    /// there is none
+    pub fn from_args(default: &str) -> InputSource {
+        match env::args().nth(1) {
+            Some(arg) => InputSource::from_arg(&arg),
+            None => InputSource::File(PathBuf::from(default)),
+        }
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+
+fn fnv(hash: u64, data: &[u8]) -> u64 {
+    data.iter().fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+/// Fingerprint of an input file (64-bit FNV-1a, as hex), so results can be
+/// matched to the input that produced them
+pub fn checksum(data: &[u8]) -> String {
+    format!("{:016x}", fnv(FNV_OFFSET, data))
+}
+
+/// A reader that fingerprints what's read through it, the way `checksum`
+/// does, for inputs that are never all in memory at once
+pub struct Checksummed<R> {
+    inner: R,
+    hash: u64,
+}
+
+impl<R: BufRead> Checksummed<R> {
+    pub fn new(inner: R) -> Checksummed<R> {
+        Checksummed { inner, hash: FNV_OFFSET }
+    }
+
+    /// The checksum of everything read so far
+    pub fn checksum(&self) -> String {
+        format!("{:016x}", self.hash)
+    }
+}
+
+impl<R: BufRead> Read for Checksummed<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.hash = fnv(self.hash, &buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<R: BufRead> BufRead for Checksummed<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // Still buffered, so this doesn't read anything
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.hash = fnv(self.hash, &buf[..amt]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksummed_matches_checksum() {
+        let data = "1000\n2000\n\n3000\n".repeat(100);
+        let mut reader = Checksummed::new(BufReader::with_capacity(7, data.as_bytes()));
+        let lines = reader.by_ref().lines().count();
+        assert_eq!(lines, 400);
+        assert_eq!(reader.checksum(), checksum(data.as_bytes()));
+    }
+}
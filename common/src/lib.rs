@@ -0,0 +1,166 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use serde::de::DeserializeOwned;
+use tracing::info_span;
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+pub mod anim;
+mod answer;
+mod config;
+pub mod explore;
+pub mod geometry;
+pub mod grid;
+mod input;
+pub mod parse;
+pub mod raster;
+
+pub use answer::{print_answers, Answer};
+pub use config::{ConfigError, NoConfig, Params, Validate};
+pub use input::{checksum, Checksummed, InputSource};
+pub use parse::ParseError;
+
+/// Which part(s) of a day's puzzle to run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    All,
+}
+
+impl Part {
+    pub fn includes(&self, part: u8) -> bool {
+        match self {
+            Part::One => part == 1,
+            Part::Two => part == 2,
+            Part::All => true,
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Part, String> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "all" => Ok(Part::All),
+            _ => Err(format!("Invalid part {:?} (expected 1, 2 or all)", s)),
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+            Part::All => write!(f, "all"),
+        }
+    }
+}
+
+/// The answer to one part of a puzzle and how long it took to compute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Everything running a day's puzzle produced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub results: Vec<PartResult>,
+}
+
+/// Parse a puzzle's input and run the selected parts against it, timing
+/// each step
+pub fn solve<T, I>(parse: impl FnOnce() -> Result<T, ParseError>, part: Part, parts: [&dyn Fn(&I) -> Answer; 2]) -> Result<Run, ParseError>
+where
+    T: Borrow<I>,
+    I: ?Sized,
+{
+    let start = Instant::now();
+    let input = info_span!("parse").in_scope(parse)?;
+    let parse_time = start.elapsed();
+
+    let results = parts.iter()
+        .zip(1..)
+        .filter(|(_, n)| part.includes(*n))
+        .map(|(f, n)| {
+            let span = match n {
+                1 => info_span!("part1"),
+                _ => info_span!("part2"),
+            };
+            let _enter = span.enter();
+
+            let start = Instant::now();
+            let answer = f(input.borrow());
+            PartResult { part: n, answer, elapsed: start.elapsed() }
+        })
+        .collect();
+
+    Ok(Run { parse_time, results })
+}
+
+/// Time a day that solves both parts in one pass over its input as it reads
+/// it, and so can't tell parsing from solving: each part is reported as
+/// taking the whole pass
+pub fn solve_streaming(part: Part, pass: impl FnOnce() -> Result<[Answer; 2], ParseError>) -> Result<Run, ParseError> {
+    let start = Instant::now();
+    let answers = info_span!("stream").in_scope(pass)?;
+    let elapsed = start.elapsed();
+
+    let results = answers.into_iter()
+        .zip(1..)
+        .filter(|(_, n)| part.includes(*n))
+        .map(|(answer, n)| PartResult { part: n, answer, elapsed })
+        .collect();
+
+    Ok(Run { parse_time: Duration::ZERO, results })
+}
+
+/// Send diagnostics to stderr, keeping stdout for answers. `RUST_LOG` picks
+/// what's shown if it's set, otherwise `verbosity` does: warnings and errors
+/// only at 0, then info, debug and trace. From info up, parsing and each part
+/// report how long they took.
+pub fn init_tracing(verbosity: u8) {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::new(match verbosity {
+            0 => "warn",
+            1 => "info",
+            2 => "debug",
+            _ => "trace",
+        })
+    });
+
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .init();
+}
+
+/// A day's puzzle: how to parse its input and solve both parts of it. Lets
+/// runners, tests and benchmarks treat every day the same way. `Config`
+/// holds the puzzle's parameters, defaulting to the real puzzle's.
+pub trait Solution {
+    type Input;
+    type Config: Default + DeserializeOwned + Validate;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input, config: &Self::Config) -> Answer;
+    fn part2(input: &Self::Input, config: &Self::Config) -> Answer;
+
+    /// Parse `input` and run the selected parts against it, timing each step
+    fn solve(input: &str, config: &Self::Config, part: Part) -> Result<Run, ParseError> {
+        solve(|| Self::parse(input), part, [&|i| Self::part1(i, config), &|i| Self::part2(i, config)])
+    }
+}
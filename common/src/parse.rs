@@ -0,0 +1,126 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+/// A problem found while parsing puzzle input, with enough context to point
+/// at the offending token.
+///
+/// `line` and `column` are 1-based. Line-level parsers usually don't know
+/// which line they are looking at, so they leave it at 0 and the caller fills
+/// it in with [`ParseError::at_line`]; the file is attached the same way by
+/// whoever opened the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    /// Why the line couldn't be read at all (or wasn't UTF-8), in which
+    /// case there's no column, nor anything expected or found
+    pub io: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(column: usize, expected: impl Into<String>, found: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line: 0,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+            io: None,
+        }
+    }
+
+    pub fn io(e: io::Error) -> ParseError {
+        ParseError { io: Some(e.to_string()), ..ParseError::new(0, "", "") }
+    }
+
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    pub fn with_file(mut self, file: impl fmt::Display) -> ParseError {
+        self.file = Some(file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(e) = &self.io {
+            return match &self.file {
+                Some(file) => write!(f, "{}:{}: can't read the input: {}", file, self.line, e),
+                None => write!(f, "line {}: can't read the input: {}", self.line, e),
+            };
+        }
+
+        match &self.file {
+            Some(file) => write!(f, "{}:{}:{}: ", file, self.line, self.column)?,
+            None => write!(f, "line {}, column {}: ", self.line, self.column)?,
+        }
+
+        if self.found.is_empty() {
+            write!(f, "expected {}, found end of line", self.expected)
+        } else {
+            write!(f, "expected {}, found {:?}", self.expected, self.found)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The lines of `reader`. A line that can't be read, or isn't UTF-8, is an
+/// error rather than the end of the input, as `map_while(Result::ok)` would
+/// have it.
+pub fn lines<R: BufRead>(reader: R) -> impl Iterator<Item = Result<String, ParseError>> {
+    reader.lines().zip(1..).map(|(l, i)| l.map_err(|e| ParseError::io(e).at_line(i)))
+}
+
+/// 1-based column at which `token` starts. `token` must be a slice of `line`.
+pub fn column_of(line: &str, token: &str) -> usize {
+    token.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+/// Parse `token`, found at `column`, as a number
+pub fn number<T: FromStr>(token: &str, column: usize) -> Result<T, ParseError> {
+    token.parse::<T>()
+        .map_err(|_| ParseError::new(column, "a number", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let e = ParseError::new(5, "a number", "x1").at_line(3);
+        assert_eq!(e.to_string(), "line 3, column 5: expected a number, found \"x1\"");
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:3:5: expected a number, found \"x1\"");
+
+        let e = ParseError::new(4, "X, Y or Z", "").at_line(1).with_file("-");
+        assert_eq!(e.to_string(), "-:1:4: expected X, Y or Z, found end of line");
+    }
+
+    #[test]
+    fn tokens() {
+        let line = "addx -12";
+        let token = line.split_once(' ').unwrap().1;
+        assert_eq!(column_of(line, token), 6);
+        assert_eq!(number::<isize>(token, 6), Ok(-12));
+        assert_eq!(number::<usize>(token, 6), Err(ParseError::new(6, "a number", "-12")));
+    }
+
+    #[test]
+    fn unreadable() {
+        let input: &[u8] = b"noop\naddx \xff\nnoop\n";
+        let mut lines = lines(input);
+        assert_eq!(lines.next(), Some(Ok("noop".to_string())));
+
+        let e = lines.next().unwrap().unwrap_err().with_file("-");
+        assert_eq!(e.to_string(), "-:2: can't read the input: stream did not contain valid UTF-8");
+    }
+}
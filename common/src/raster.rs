@@ -0,0 +1,124 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use crate::geometry::Point2;
+use crate::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// A picture made of indices into a palette, so a day can draw with colours
+/// that mean something ("rock", "sand") and choose what they look like
+/// separately
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub pixels: Grid<u8>,
+    pub palette: Vec<Rgb>,
+}
+
+impl Image {
+    /// Panics if a pixel isn't in the palette
+    pub fn new(pixels: Grid<u8>, palette: &[Rgb]) -> Image {
+        if let Some(i) = pixels.iter().find(|&&i| i as usize >= palette.len()) {
+            panic!("Colour {} isn't in a palette of {}", i, palette.len());
+        }
+
+        Image { pixels, palette: palette.to_vec() }
+    }
+
+    /// Each pixel blown up into a `factor` x `factor` square
+    pub fn scaled(&self, factor: usize) -> Image {
+        let (width, height) = (self.pixels.width() * factor, self.pixels.height() * factor);
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point2::new(x / factor, y / factor)))
+            .map(|p| self.pixels[p])
+            .collect();
+
+        Image { pixels: Grid::from_vec(width, cells), palette: self.palette.clone() }
+    }
+
+    /// Binary PPM (P6)
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.pixels.width(), self.pixels.height())?;
+        let data = self.pixels.iter()
+            .flat_map(|&i| {
+                let Rgb(r, g, b) = self.palette[i as usize];
+                [r, g, b]
+            })
+            .collect::<Vec<_>>();
+        out.write_all(&data)
+    }
+
+    /// Indexed PNG, the palette becoming the PNG's
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.pixels.width() as u32, self.pixels.height() as u32);
+        encoder.set_color(png::ColorType::Indexed);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_palette(self.palette.iter().flat_map(|&Rgb(r, g, b)| [r, g, b]).collect::<Vec<_>>());
+
+        let data = self.pixels.iter().copied().collect::<Vec<_>>();
+        encoder.write_header()
+            .and_then(|mut w| w.write_image_data(&data))
+            .map_err(io::Error::other)
+    }
+
+    /// A PPM if the file name ends in `.ppm`, a PNG otherwise
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut out = BufWriter::new(File::create(path)?);
+        match path.extension() {
+            Some(ext) if ext.eq_ignore_ascii_case("ppm") => self.write_ppm(&mut out)?,
+            _ => self.write_png(&mut out)?,
+        }
+        out.flush()
+    }
+}
+
+/// `n` colours fading evenly from `from` to `to`
+pub fn gradient(from: Rgb, to: Rgb, n: usize) -> Vec<Rgb> {
+    let mix = |a: u8, b: u8, i: usize| {
+        let t = if n > 1 { i as f64 / (n - 1) as f64 } else { 0.0 };
+        (a as f64 + (b as f64 - a as f64) * t).round() as u8
+    };
+
+    (0..n)
+        .map(|i| Rgb(mix(from.0, to.0, i), mix(from.1, to.1, i), mix(from.2, to.2, i)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Image {
+        Image::new(Grid::from_vec(2, vec![0, 1, 1, 0]), &[Rgb(0, 0, 0), Rgb(255, 128, 0)])
+    }
+
+    #[test]
+    fn ppm() {
+        let mut out = Vec::new();
+        checkerboard().write_ppm(&mut out).unwrap();
+
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([0, 0, 0, 255, 128, 0, 255, 128, 0, 0, 0, 0]);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn png_round_trips() {
+        let image = checkerboard().scaled(3);
+        let mut out = Vec::new();
+        image.write_png(&mut out).unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        assert_eq!(reader.info().palette.as_deref(), Some(&[0, 0, 0, 255, 128, 0][..]));
+        let mut data = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut data).unwrap();
+        assert_eq!(data, image.pixels.iter().copied().collect::<Vec<_>>());
+        assert_eq!(&data[..6], [0, 0, 0, 1, 1, 1]);
+    }
+
+    #[test]
+    fn gradients() {
+        assert_eq!(gradient(Rgb(0, 0, 0), Rgb(10, 20, 30), 3), [Rgb(0, 0, 0), Rgb(5, 10, 15), Rgb(10, 20, 30)]);
+    }
+}
@@ -0,0 +1,130 @@
+use std::io::prelude::*;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+pub type Input = Vec<usize>;
+
+// Small enough that the three biggest totals add up to an answer
+const MAX_TOTAL: usize = i64::MAX as usize / 3;
+
+// Calories on line `i` (0-based) added to `total`, or None for a blank line
+// between elves
+fn add_calories(total: usize, l: &str, i: usize) -> Result<Option<usize>, ParseError> {
+    if l.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let calories = parse::number::<usize>(l, 1)
+        .map_err(|e| e.at_line(i + 1))?;
+    total.checked_add(calories)
+        .filter(|&t| t <= MAX_TOTAL)
+        .map(Some)
+        .ok_or_else(|| ParseError::new(1, format!("a total of at most {}", MAX_TOTAL), l).at_line(i + 1))
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .try_fold(vec![0usize], |mut acc, (i, l)| {
+            let total = acc.last_mut().unwrap();
+            match add_calories(*total, &l?, i)? {
+                Some(t) => *total = t,
+                None => acc.push(0),
+            }
+            Ok(acc)
+        })
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+pub fn part_1(elves: &[usize]) -> Answer {
+    (*elves.iter().max().unwrap()).into()
+}
+
+pub fn part_2(elves: &[usize]) -> Answer {
+    let mut elves = elves.to_vec();
+    elves.sort_unstable();
+    elves.iter().rev().take(3).sum::<usize>().into()
+}
+
+// Keep `total` if it's among the three biggest, biggest first
+fn keep_top(top: &mut [usize; 3], total: usize) {
+    if total > top[2] {
+        top[2] = total;
+        top.sort_unstable_by(|a, b| b.cmp(a));
+    }
+}
+
+/// Both parts in one pass over the input as it's read, remembering only the
+/// three biggest totals so far
+pub fn stream<R: BufRead>(reader: R, part: Part) -> Result<Run, ParseError> {
+    common::solve_streaming(part, || {
+        let mut top = [0; 3];
+        let mut total = 0;
+        for (i, l) in parse::lines(reader).enumerate() {
+            let l = l?;
+            match add_calories(total, &l, i)? {
+                Some(t) => total = t,
+                None => {
+                    keep_top(&mut top, total);
+                    total = 0;
+                },
+            }
+        }
+        keep_top(&mut top, total);
+
+        Ok([top[0].into(), top.iter().sum::<usize>().into()])
+    })
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(24000));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(45000));
+    }
+
+    #[test]
+    fn stream_example() {
+        let run = stream(EXAMPLE.as_bytes(), Part::All).unwrap();
+        let answers = run.results.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers, [Answer::Int(24000), Answer::Int(45000)]);
+    }
+}
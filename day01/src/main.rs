@@ -1,38 +1,20 @@
-use std::io::prelude::*;
-use std::io::BufReader;
-use std::fs::File;
+use common::{print_answers, InputSource, Part};
+use std::process;
 
-fn read_input() -> Vec<usize> {
-    let input_file = File::open("src/input.txt").unwrap();
-    let reader = BufReader::new(input_file);
-    reader.lines()
-        .map(|x|x.unwrap())
-        .fold(vec![vec![]], |mut acc, l| {
-            if l.trim().is_empty() {
-                acc.push(vec![]);
-            } else {
-                acc.last_mut().unwrap().push(l.parse::<usize>().unwrap());
-            }
-            acc
-        })
-        .iter()
-        .map(|e| e.iter().sum())
-        .collect()
-}
-
-fn part_1(elves: &Vec<usize>) {
-    println!("Max is {:?}", elves.iter().max().unwrap());
-}
+fn main() {
+    common::init_tracing(0);
 
-fn part_2(elves: &Vec<usize>) {
-    let mut elves = elves.clone();
-    elves.sort_unstable();
-    println!("Max is {:?}", elves.iter().rev().take(3).sum::<usize>());
-}
+    let source = InputSource::from_args(day01::DEFAULT_INPUT);
+    let mut reader = source.open().unwrap_or_else(|e| {
+        eprintln!("Can't open {}: {}", source, e);
+        process::exit(1);
+    });
 
-fn main() {
-    let input = read_input();
-    part_1(&input);
-    part_2(&input);
-    //println!("{:?}", input);
+    match day01::run(&mut reader, Part::All) {
+        Ok(run) => print_answers(&run.results),
+        Err(e) => {
+            eprintln!("{}", e.with_file(&source));
+            process::exit(1);
+        }
+    }
 }
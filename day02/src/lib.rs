@@ -0,0 +1,164 @@
+use std::io::prelude::*;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug)]
+struct Round {
+    me: u8,
+    them: u8,
+}
+
+impl Round {
+    fn score(&self) -> usize {
+        let me = self.me as usize + 1;
+        if self.me == (self.them + 1)%3 {
+            return me + 6;
+        }
+
+        if self.them == self.me {
+            return 3 + me;
+        }
+
+        me
+    }
+}
+
+fn convert(s: &str, column: usize) -> Result<u8, ParseError> {
+    match s {
+        "X" => Ok(0),
+        "Y" => Ok(1),
+        "Z" => Ok(2),
+        _ => Err(ParseError::new(column, "X, Y or Z", s)),
+    }
+}
+
+fn convert_me(s: u8, them: u8) -> u8 {
+    match s {
+        0 => (them + 2) % 3,
+        1 => them,
+        _ => (them + 1) %3,
+    }
+}
+
+fn convert_them(s: &str, column: usize) -> Result<u8, ParseError> {
+    match s {
+        "A" => Ok(0),
+        "B" => Ok(1),
+        "C" => Ok(2),
+        _ => Err(ParseError::new(column, "A, B or C", s)),
+    }
+}
+
+// Each line is (their shape, second column) - what the second column means
+// depends on the part
+pub type Input = Vec<(u8, u8)>;
+
+fn parse_line(l: &str) -> Result<(u8, u8), ParseError> {
+    let mut parts = l.split(' ');
+    let them = convert_them(parts.next().unwrap_or(""), 1)?;
+    let (second, end) = match parts.next() {
+        Some(p) => {
+            let column = parse::column_of(l, p);
+            (convert(p, column)?, column + p.len())
+        },
+        None => return Err(ParseError::new(l.len() + 1, "X, Y or Z", "")),
+    };
+
+    let rest = &l[end - 1..];
+    if !rest.trim_end().is_empty() {
+        return Err(ParseError::new(end, "end of line", rest));
+    }
+
+    Ok((them, second))
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| parse_line(&l).map_err(|e| e.at_line(i + 1))))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+fn rounds_1(input: &Input) -> Vec<Round> {
+    input.iter()
+        .map(|&(them, me)| Round{me,them})
+        .collect()
+}
+
+fn rounds_2(input: &Input) -> Vec<Round> {
+    input.iter()
+        .map(|&(them, outcome)| {
+            let me = convert_me(outcome, them);
+            Round{me,them}
+        })
+        .collect()
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    rounds_1(input).iter()
+        .map(|x|x.score())
+        .sum::<usize>()
+        .into()
+}
+
+pub fn part_2(input: &Input) -> Answer {
+    rounds_2(input).iter()
+        .map(|x|x.score())
+        .sum::<usize>()
+        .into()
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("A Y\nB Q\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:2:3: expected X, Y or Z, found \"Q\"");
+        assert_eq!(parse("C").unwrap_err().to_string(), "line 1, column 2: expected X, Y or Z, found end of line");
+        assert_eq!(parse("A X Z").unwrap_err().to_string(), "line 1, column 4: expected end of line, found \" Z\"");
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(15));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(12));
+    }
+}
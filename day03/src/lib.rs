@@ -0,0 +1,136 @@
+use std::io::prelude::*;
+use std::collections::HashSet;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug)]
+pub struct Rucksack {
+    h1: Vec<usize>,
+    h2: Vec<usize>,
+}
+
+impl Rucksack {
+    fn new(r: (&[usize], &[usize])) -> Rucksack {
+        Rucksack { h1: r.0.to_vec(), h2: r.1.to_vec() }
+    }
+}
+
+fn convert(c: &char, column: usize) -> Result<usize, ParseError> {
+    if !c.is_ascii_alphabetic() {
+        return Err(ParseError::new(column, "an item letter", c.to_string()));
+    }
+
+    if c.is_uppercase() {
+        Ok(*c as usize - 'A' as usize + 27)
+    } else {
+        Ok(*c as usize - 'a' as usize + 1)
+    }
+}
+
+// The item both halves have
+fn common_item(rs: &Rucksack) -> Option<usize> {
+    rs.h1.iter().find(|i| rs.h2.contains(i)).copied()
+}
+
+// The item all three of a group have
+fn badge(group: &[Rucksack]) -> Option<usize> {
+    group.iter()
+        .map(|rs| rs.h1.iter().chain(rs.h2.iter()).cloned().collect::<HashSet<_>>())
+        .reduce(|a, b| a.intersection(&b).cloned().collect())
+        .and_then(|items| items.into_iter().next())
+}
+
+fn parse_line(l: &str) -> Result<Rucksack, ParseError> {
+    let items = l.chars()
+        .enumerate()
+        .map(|(i, c)| convert(&c, i + 1))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Letters only, so a byte each
+    let rs = Rucksack::new(items.split_at(items.len() / 2));
+    if common_item(&rs).is_none() {
+        return Err(ParseError::new(l.len() / 2 + 1, "a second half with an item from the first", &l[l.len() / 2..]));
+    }
+
+    Ok(rs)
+}
+
+pub type Input = Vec<Rucksack>;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let lines = parse::lines(reader).collect::<Result<Vec<_>, _>>()?;
+    let input = lines.iter()
+        .enumerate()
+        .map(|(i, l)| parse_line(l).map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<Input, _>>()?;
+
+    // Part 2 needs every (whole) group of three to have a badge
+    if let Some(g) = input.chunks_exact(3).position(|group| badge(group).is_none()) {
+        let l = g * 3 + 2;
+        return Err(ParseError::new(1, "a rucksack with an item the two before it have", lines[l].as_str()).at_line(l + 1));
+    }
+
+    Ok(input)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+pub fn part_1(input: &[Rucksack]) -> Answer {
+    input.iter()
+        .map(|rs| common_item(rs).unwrap())
+        .sum::<usize>()
+        .into()
+}
+
+pub fn part_2(input: &[Rucksack]) -> Answer {
+    input.chunks_exact(3)
+        .map(|group| badge(group).unwrap())
+        .sum::<usize>()
+        .into()
+}
+
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(157));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(70));
+    }
+}
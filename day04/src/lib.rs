@@ -0,0 +1,149 @@
+use std::io::prelude::*;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Copy, Clone)]
+pub struct Range {
+    beginning: usize,
+    end: usize,
+}
+
+impl Range {
+    fn new(beginning: usize, end: usize) -> Range {
+        Range{beginning, end}
+    }
+
+    // `column` is where `s` starts in its line
+    fn from_str(s: &str, column: usize) -> Result<Range, ParseError> {
+        let (b, e) = s.split_once('-')
+            .ok_or_else(|| ParseError::new(column + s.len(), "'-'", ""))?;
+
+        let e_column = column + parse::column_of(s, e) - 1;
+        let (beginning, end) = (parse::number(b, column)?, parse::number(e, e_column)?);
+        if end < beginning {
+            return Err(ParseError::new(e_column, format!("an end no lower than {}", beginning), e));
+        }
+
+        Ok(Range::new(beginning, end))
+    }
+
+    fn contains(&self, other: &Range) -> bool {
+        other.beginning >= self.beginning &&
+            other.end <= self.end
+    }
+
+    fn overlap(&self, other: &Range) -> bool {
+        (self.end >= other.beginning && self.end <= other.end) ||
+            (other.end >= self.beginning && other.end <= self.end)
+    }
+}
+
+fn parse_line(l: &str) -> Result<(Range, Range), ParseError> {
+    let (r1, r2) = l.split_once(',')
+        .ok_or_else(|| ParseError::new(l.len() + 1, "','", ""))?;
+
+    Ok((Range::from_str(r1, 1)?, Range::from_str(r2, parse::column_of(l, r2))?))
+}
+
+pub type Input = Vec<(Range, Range)>;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| parse_line(&l).map_err(|e| e.at_line(i + 1))))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+pub fn part_1(input: &[(Range,Range)]) -> Answer {
+    input.iter()
+        .filter(|p| p.0.contains(&p.1) || p.1.contains(&p.0))
+        .count()
+        .into()
+}
+
+pub fn part_2(input: &[(Range,Range)]) -> Answer {
+    input.iter()
+        .filter(|p| p.0.overlap(&p.1))
+        .count()
+        .into()
+}
+
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("2-4,6-8\n5-2,1-3\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:2:3: expected an end no lower than 5, found \"2\"");
+        assert_eq!(parse("2-4,8-6").unwrap_err().column, 7);
+        assert_eq!(parse("2-4;6-8").unwrap_err().column, 8);
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(2));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(4));
+    }
+
+    fn range() -> impl Strategy<Value = Range> {
+        (0..20usize, 0..20usize).prop_map(|(a, b)| Range::new(a.min(b), a.max(b)))
+    }
+
+    fn sections(r: &Range) -> std::ops::RangeInclusive<usize> {
+        r.beginning..=r.end
+    }
+
+    proptest! {
+        #[test]
+        fn overlap_matches_brute_force(a in range(), b in range()) {
+            let expected = sections(&a).any(|s| sections(&b).contains(&s));
+            prop_assert_eq!(a.overlap(&b), expected);
+            prop_assert_eq!(b.overlap(&a), expected);
+        }
+
+        #[test]
+        fn contains_matches_brute_force(a in range(), b in range()) {
+            let expected = sections(&b).all(|s| sections(&a).contains(&s));
+            prop_assert_eq!(a.contains(&b), expected);
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::io::prelude::*;
+use regex::Regex;
+use lazy_static::lazy_static;
+use tracing::trace;
+use std::collections::HashMap;
+use std::iter;
+use common::anim::Frame;
+use common::geometry::Point2;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Copy, Clone)]
+pub struct Move {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+pub type Input = (HashMap<usize, Vec<char>>, Vec<Move>);
+
+// Crates on a line of the drawing, as (stack, crate). Each stack takes up
+// four columns: `[X] ` for a crate, blanks for none.
+fn convert_line(l: &str) -> Result<Vec<(usize, char)>, ParseError> {
+    let l = l.as_bytes();
+    let mut res = Vec::new();
+    for i in (0..l.len()).step_by(4) {
+        match &l[i..usize::min(i + 3, l.len())] {
+            [b'[', c, b']'] if c.is_ascii_alphabetic() => res.push((i/4 + 1, *c as char)),
+            cell if cell.iter().all(|c| *c == b' ') => (),
+            cell => return Err(ParseError::new(i + 1, "a crate ([X]) or blanks", String::from_utf8_lossy(cell))),
+        }
+    }
+
+    Ok(res)
+}
+
+// `heights` are how many crates each stack has before the move, and after it
+// once it's parsed: a move can't take more crates than there are
+fn parse_move(l: &str, heights: &mut HashMap<usize, usize>) -> Result<Move, ParseError> {
+    lazy_static! {
+        static ref MOVE_REGEX: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+    }
+
+    let c = MOVE_REGEX.captures(l)
+        .ok_or_else(|| ParseError::new(1, "move N from N to N", l))?;
+    let num = |i| {
+        let m = c.get(i).unwrap();
+        parse::number::<usize>(m.as_str(), m.start() + 1)
+    };
+    let stack = |i| {
+        let m = c.get(i).unwrap();
+        num(i).and_then(|n| match heights.contains_key(&n) {
+            true => Ok(n),
+            false => Err(ParseError::new(m.start() + 1, "an existing stack", m.as_str())),
+        })
+    };
+
+    let m = Move{
+        quantity: num(1)?,
+        from: stack(2)?,
+        to: stack(3)?,
+    };
+    if m.quantity > heights[&m.from] {
+        let quantity = c.get(1).unwrap();
+        let expected = format!("at most the {} crates stack {} has", heights[&m.from], m.from);
+        return Err(ParseError::new(quantity.start() + 1, expected, quantity.as_str()));
+    }
+
+    *heights.get_mut(&m.from).unwrap() -= m.quantity;
+    *heights.get_mut(&m.to).unwrap() += m.quantity;
+    Ok(m)
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let lines = parse::lines(reader).collect::<Result<Vec<_>, _>>()?;
+
+    let drawing = lines.iter()
+        .take_while(|l| !l.trim().is_empty())
+        .collect::<Vec<_>>();
+
+    // Bottom up, skipping the line of stack numbers
+    let mut stacks = HashMap::new();
+    for (i, l) in drawing.iter().enumerate().rev().skip(1) {
+        for (stack, c) in convert_line(l).map_err(|e| e.at_line(i + 1))? {
+            stacks.entry(stack).or_insert(vec![]).push(c);
+        }
+    }
+
+    let mut heights = stacks.iter().map(|(s, crates)| (*s, crates.len())).collect();
+    let moves = lines.iter()
+        .enumerate()
+        .skip(drawing.len() + 1)
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| parse_move(l, &mut heights).map_err(|e| e.at_line(i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((stacks, moves))
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// The crate on top of each stack, in stack order. Stacks that never had
+// any crates aren't there at all, so the numbers can have gaps.
+fn tops(stacks: &HashMap<usize, Vec<char>>) -> Answer {
+    let mut numbers = stacks.keys().collect::<Vec<_>>();
+    numbers.sort();
+    numbers.into_iter()
+        .filter_map(|i| stacks[i].last())
+        .collect::<String>()
+        .into()
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    let mut stacks = input.0.clone();
+    for m in input.1.iter() {
+        trace!(?m);
+        for _ in 0..m.quantity {
+            let item = stacks.get_mut(&m.from).unwrap().pop().unwrap();
+            stacks.get_mut(&m.to).unwrap().push(item);
+        }
+    }
+
+    tops(&stacks)
+}
+
+pub fn part_2(input: &Input) -> Answer {
+    let mut stacks = input.0.clone();
+    for m in input.1.iter() {
+        trace!(?m);
+        let mut interim = vec![];
+        for _ in 0..m.quantity {
+            interim.push(stacks.get_mut(&m.from).unwrap().pop().unwrap());
+        }
+
+        while let Some(element) = interim.pop() {
+            stacks.get_mut(&m.to).unwrap().push(element);
+        }
+    }
+
+    tops(&stacks)
+}
+
+// Where stack `stack`'s crate `i` (counting from the bottom) is drawn. The
+// stack numbers go underneath, on line 0.
+fn crate_at(stack: usize, i: usize) -> Point2<isize> {
+    Point2::new((stack as isize - 1) * 4, -(i as isize) - 1)
+}
+
+/// The crane at work: a crate at a time if `one_at_a_time` (the
+/// CrateMover 9000 of part 1), otherwise a whole move at a time (the 9001)
+pub fn steps(input: &Input, one_at_a_time: bool) -> impl Iterator<Item = Frame> + '_ {
+    let mut stacks = input.0.clone();
+
+    let mut start = Frame::new(Point2::new(0, 0), "Start");
+    for (&stack, crates) in stacks.iter() {
+        start.text(Point2::new(crate_at(stack, 0).x + 1, 0), &stack.to_string());
+        for (i, c) in crates.iter().enumerate() {
+            start.text(crate_at(stack, i), &format!("[{}]", c));
+        }
+    }
+
+    let lifts = input.1.iter()
+        .enumerate()
+        .flat_map(move |(i, m)| match one_at_a_time {
+            true => iter::repeat_n((i, m, 1), m.quantity),
+            false => iter::repeat_n((i, m, m.quantity), 1),
+        });
+
+    iter::once(start).chain(lifts.map(move |(i, m, quantity)| {
+        let caption = format!("Move {} of {}: move {} from {} to {}", i + 1, input.1.len(), m.quantity, m.from, m.to);
+        let mut frame = Frame::new(Point2::new(0, 0), caption);
+
+        let from = stacks.get_mut(&m.from).unwrap();
+        let lifted = from.split_off(from.len() - quantity);
+        for k in from.len()..from.len() + quantity {
+            frame.text(crate_at(m.from, k), "   ");
+        }
+
+        let to = stacks.get_mut(&m.to).unwrap();
+        for c in lifted {
+            frame.text(crate_at(m.to, to.len()), &format!("[{}]", c));
+            to.push(c);
+        }
+
+        frame.focus = crate_at(m.to, to.len() - 1);
+        frame
+    }))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::anim::Canvas;
+    use common::geometry::Vec2;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::from("CMZ"));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::from("MCD"));
+    }
+
+    #[test]
+    fn steps_end_like_the_parts() {
+        let input = parse(EXAMPLE).unwrap();
+        for (one_at_a_time, expected) in [(true, "CMZ"), (false, "MCD")] {
+            let mut canvas = Canvas::new();
+            steps(&input, one_at_a_time).for_each(|f| canvas.apply(&f));
+
+            let tops = (1..=3)
+                .filter_map(|stack| (0..)
+                    .take_while(|&i| canvas.get(crate_at(stack, i)) == '[')
+                    .last()
+                    .map(|i| canvas.get(crate_at(stack, i) + Vec2::new(1, 0))))
+                .collect::<String>();
+            assert_eq!(tops, expected);
+        }
+    }
+}
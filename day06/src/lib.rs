@@ -0,0 +1,237 @@
+use std::io::prelude::*;
+use std::collections::{HashSet, VecDeque};
+use serde::Deserialize;
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+pub type Input = Vec<char>;
+
+/// How many distinct characters in a row make each kind of marker
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub packet_window: usize,
+    pub message_window: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { packet_window: 4, message_window: 14 }
+    }
+}
+
+// No marker's longer than the alphabet, as its letters are all different
+const MAX_WINDOW: usize = 26;
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        for (name, window) in [("packet_window", self.packet_window), ("message_window", self.message_window)] {
+            if !(1..=MAX_WINDOW).contains(&window) {
+                return Err(format!("{} must be from 1 to {}, not {}", name, MAX_WINDOW, window));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let mut res = Vec::new();
+    for (i, l) in parse::lines(reader).enumerate() {
+        let l = l?;
+        if let Some((j, c)) = l.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(ParseError::new(j + 1, "a lowercase letter", c.to_string()).at_line(i + 1));
+        }
+
+        res.extend(l.bytes().map(|b| b as char));
+    }
+
+    Ok(res)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// Number of characters read by the end of the first `window` distinct ones
+fn find_marker(input: &Input, window: usize) -> Option<usize> {
+    let first_marker =
+        input.as_slice()
+         .windows(window)
+         .enumerate()
+         .find(|x| HashSet::<&char>::from_iter(x.1.iter()).len() == window)?;
+
+    Some(first_marker.0 + window)
+}
+
+fn marker_answer(marker: Option<usize>) -> Answer {
+    match marker {
+        Some(n) => n.into(),
+        None => "Not found!".into(),
+    }
+}
+
+pub fn part_1(input: &Input, config: &Config) -> Answer {
+    marker_answer(find_marker(input, config.packet_window))
+}
+
+pub fn part_2(input: &Input, config: &Config) -> Answer {
+    marker_answer(find_marker(input, config.message_window))
+}
+
+
+// The letters in the last `size` characters, and whether they were all
+// different when the first marker came by
+struct Window {
+    size: usize,
+    counts: [usize; 26],
+    distinct: usize,
+    marker: Option<usize>,
+}
+
+impl Window {
+    fn new(size: usize) -> Window {
+        Window { size, counts: [0; 26], distinct: 0, marker: None }
+    }
+
+    // `c` being the `n`th character, and `dropped` the one that's no longer
+    // in the window as a result
+    fn push(&mut self, c: u8, dropped: Option<u8>, n: usize) {
+        self.counts[(c - b'a') as usize] += 1;
+        if self.counts[(c - b'a') as usize] == 1 {
+            self.distinct += 1;
+        }
+        if let Some(d) = dropped {
+            self.counts[(d - b'a') as usize] -= 1;
+            if self.counts[(d - b'a') as usize] == 0 {
+                self.distinct -= 1;
+            }
+        }
+
+        if self.distinct == self.size && self.marker.is_none() {
+            self.marker = Some(n);
+        }
+    }
+}
+
+/// Both parts in one pass over the input as it's read, a byte at a time, so
+/// even a single line too long to hold in memory will do
+pub fn stream<R: BufRead>(reader: R, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve_streaming(part, || {
+        let mut windows = [Window::new(config.packet_window), Window::new(config.message_window)];
+        let longest = config.packet_window.max(config.message_window);
+        let mut last = VecDeque::with_capacity(longest + 1);
+        let (mut line, mut column, mut n) = (1, 0, 0);
+
+        // A carriage return's only allowed at the end of a line, as for
+        // read_input, so whether it is waits for the next byte
+        let mut cr = false;
+        for b in reader.bytes() {
+            let b = b.map_err(|e| ParseError::io(e).at_line(line))?;
+            match (b, cr) {
+                (b'\n', _) => {
+                    line += 1;
+                    column = 0;
+                    cr = false;
+                    continue;
+                },
+                (_, true) => return Err(ParseError::new(column, "a lowercase letter", "\r").at_line(line)),
+                (b'\r', false) => {
+                    column += 1;
+                    cr = true;
+                    continue;
+                },
+                _ => column += 1,
+            }
+
+            if !b.is_ascii_lowercase() {
+                let found = String::from_utf8_lossy(&[b]).into_owned();
+                return Err(ParseError::new(column, "a lowercase letter", found).at_line(line));
+            }
+
+            n += 1;
+            last.push_back(b);
+            for w in windows.iter_mut() {
+                let dropped = (last.len() > w.size).then(|| last[last.len() - 1 - w.size]);
+                w.push(b, dropped, n);
+            }
+            if last.len() > longest {
+                last.pop_front();
+            }
+        }
+
+        if cr {
+            return Err(ParseError::new(column, "a lowercase letter", "\r").at_line(line));
+        }
+
+        Ok(windows.map(|w| marker_answer(w.marker)))
+    })
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn config_error() {
+        assert!(Config { packet_window: 0, message_window: 14 }.validate().is_err());
+        assert!(Config { packet_window: 4, message_window: 4294967295000 }.validate().is_err());
+        assert!(Config { packet_window: 4, message_window: 26 }.validate().is_ok());
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &Config::default()), Answer::Int(7));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input, &Config::default()), Answer::Int(19));
+    }
+
+    #[test]
+    fn stream_examples() {
+        for (example, markers) in [
+            ("mjqjpqmgbljsphdztnvjfqwrcgsmlb\n", [7, 19]),
+            ("bvwbjplbgvbhsrlpgdmjqwftvncz", [5, 23]),
+            ("nppdvjthqldpwncqszvftbrmjlhg\r\n", [6, 23]),
+            ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", [10, 29]),
+            ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", [11, 26]),
+        ] {
+            let run = stream(example.as_bytes(), &Config::default(), Part::All).unwrap();
+            let answers = run.results.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+            assert_eq!(answers, markers.map(Answer::Int));
+        }
+
+        let e = stream("abc\nabC".as_bytes(), &Config::default(), Part::All).unwrap_err();
+        assert_eq!(e, parse("abc\nabC").unwrap_err());
+    }
+}
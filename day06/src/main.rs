@@ -1,45 +1,24 @@
-use std::io::prelude::*;
-use std::io::BufReader;
-use std::fs::File;
-use std::collections::HashSet;
-
-type Input = Vec<char>;
-
-fn read_input() -> Input {
-    let input_file = File::open("src/input.txt").unwrap();
-    let reader = BufReader::new(input_file);
-    reader.lines()
-        .filter_map(|l| l.ok())
-        .flat_map(|l| l.into_bytes())
-        .map(|b| b as char)
-        .collect()
-}
-
-fn part_1(input: &Input) {
-    let first_packet_marker =
-        input.as_slice()
-         .windows(4)
-         .enumerate()
-         .find(|x| HashSet::<&char>::from_iter(x.1.iter()).len() == 4)
-         .unwrap();
-
-    println!("First location {:?}", first_packet_marker.0 + 4);
-}
-
-fn part_2(input: &Input) {
-    let first_packet_marker =
-        input.as_slice()
-         .windows(14)
-         .enumerate()
-         .find(|x| HashSet::<&char>::from_iter(x.1.iter()).len() == 14)
-         .unwrap();
-
-    println!("First location {:?}", first_packet_marker.0 + 14);
-}
-
+use common::{print_answers, InputSource, Params, Part};
+use std::process;
 
 fn main() {
-    let input = read_input();
-    let s1 = part_1(&input);
-    let s2 = part_2(&input);
+    common::init_tracing(0);
+
+    let source = InputSource::from_args(day06::DEFAULT_INPUT);
+    let config = Params::for_input(&source).and_then(|p| p.get()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let mut reader = source.open().unwrap_or_else(|e| {
+        eprintln!("Can't open {}: {}", source, e);
+        process::exit(1);
+    });
+
+    match day06::run(&mut reader, &config, Part::All) {
+        Ok(run) => print_answers(&run.results),
+        Err(e) => {
+            eprintln!("{}", e.with_file(&source));
+            process::exit(1);
+        }
+    }
 }
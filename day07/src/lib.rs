@@ -0,0 +1,506 @@
+use std::io::prelude::*;
+use regex::Regex;
+use std::rc::{Rc, Weak};
+use std::cell::RefCell;
+use lazy_static::lazy_static;
+use tracing::{debug, enabled, info, trace, Level};
+use std::iter::once;
+use serde::Deserialize;
+use common::explore::Explore;
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+pub struct FileDetails {
+    name: String,
+    size: usize,
+}
+
+#[derive(Debug, Clone)]
+pub enum DirEntry {
+    Dir(String),
+    File(FileDetails),
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    List(Vec<DirEntry>),
+    ChangeDirectory(String),
+}
+
+
+#[derive(Clone)]
+struct Node {
+    name: String,
+    files: Vec<DirEntry>,
+    dirs: Vec<Rc<RefCell<Node>>>,
+    parent: Weak<RefCell<Node>>,
+    total_size: usize,
+}
+
+impl Node {
+    fn new(name: &str) -> Rc<RefCell<Node>> {
+        Rc::new(RefCell::new(Node { name: name.to_string(), files: vec![], dirs: vec![], parent: Weak::new(), total_size: 0}))
+    }
+
+    fn new_with_parent(name: &str, parent: Weak<RefCell<Node>>) -> Rc<RefCell<Node>> {
+        Rc::new(RefCell::new(Node { name: name.to_string(), files: vec![], dirs: vec![], parent, total_size: 0}))
+    }
+}
+
+
+impl DirEntry {
+    fn new(s: &str) -> Result<DirEntry, ParseError> {
+        lazy_static! {
+            static ref DIR_REGEX: Regex = Regex::new(r"^dir (.+)$").unwrap();
+            static ref FILE_REGEX: Regex = Regex::new(r"^(\d+) (.+)$").unwrap();
+        }
+
+        if let Some(c) = DIR_REGEX.captures(s) {
+            Ok(DirEntry::Dir(c.get(1).unwrap().as_str().to_string()))
+        } else if let Some(c) = FILE_REGEX.captures(s) {
+            Ok(DirEntry::File(FileDetails::new(
+                parse::number(c.get(1).unwrap().as_str(), 1)?, c.get(2).unwrap().as_str()
+            )))
+        } else {
+            Err(ParseError::new(1, "a command or a `dir NAME`/`SIZE NAME` entry", s))
+        }
+    }
+}
+
+impl FileDetails {
+    fn new(size: usize, name: &str) -> FileDetails {
+        FileDetails{size, name: name.to_string()}
+    }
+}
+
+pub type Input = Vec<Command>;
+
+/// The disk's size and the free space the update needs (part 2)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub disk_size: usize,
+    pub needed_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { disk_size: 70000000, needed_size: 30000000 }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.needed_size > self.disk_size {
+            return Err(format!("needed_size ({}) can't be more than disk_size ({})", self.needed_size, self.disk_size));
+        }
+
+        Ok(())
+    }
+}
+
+// One line of the terminal output
+enum Line {
+    Cd(String),
+    Ls,
+    Entry(DirEntry),
+}
+
+fn parse_line(line: &str) -> Result<Line, ParseError> {
+    lazy_static! {
+        static ref CD_REGEX: Regex = Regex::new(r"^\$\s+cd\s+(.*)$").unwrap();
+        static ref LS_REGEX: Regex = Regex::new(r"^\$\s*ls\s*$").unwrap();
+    }
+
+    if let Some(c) = CD_REGEX.captures(line) {
+        Ok(Line::Cd(c.get(1).unwrap().as_str().to_string()))
+    } else if LS_REGEX.is_match(line) {
+        Ok(Line::Ls)
+    } else {
+        DirEntry::new(line).map(Line::Entry)
+    }
+}
+
+// Checks lines as they're read, in order: directory entries only come in a
+// `$ ls` listing (up to the next command), and the files listed add up to
+// no more than an answer holds, so that no directory's size can overflow
+#[derive(Default)]
+struct Checker {
+    listing: bool,
+    total: usize,
+}
+
+impl Checker {
+    fn line(&mut self, line: &str) -> Result<Line, ParseError> {
+        let parsed = parse_line(line)?;
+        match &parsed {
+            Line::Cd(_) => self.listing = false,
+            Line::Ls => self.listing = true,
+            Line::Entry(_) if !self.listing => {
+                return Err(ParseError::new(1, "a command (entries only follow `$ ls`)", line));
+            },
+            Line::Entry(DirEntry::File(fd)) => {
+                self.total = self.total.checked_add(fd.size)
+                    .filter(|&t| t <= i64::MAX as usize)
+                    .ok_or_else(|| ParseError::new(1, format!("files adding up to at most {}", i64::MAX), fd.size.to_string()))?;
+            },
+            Line::Entry(DirEntry::Dir(_)) => (),
+        }
+
+        Ok(parsed)
+    }
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let mut res = Vec::new();
+    let mut checker = Checker::default();
+    for (i, line) in parse::lines(reader).enumerate() {
+        let line = line?;
+        match checker.line(&line).map_err(|e| e.at_line(i + 1))? {
+            Line::Cd(dir) => {
+                trace!("Changing directory: {}", line);
+                res.push(Command::ChangeDirectory(dir));
+            },
+            Line::Ls => {
+                trace!("Listing: {}", line);
+                res.push(Command::List(Vec::new()));
+            },
+            Line::Entry(de) => {
+                // The checker saw to it there's a listing to add it to
+                trace!("Adding {} to contents: {:?}", line, de);
+                if let Some(Command::List(contents)) = res.last_mut() {
+                    contents.push(de);
+                }
+            },
+        }
+    }
+
+    Ok(res)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+fn build_tree(input: &Input) -> Rc<RefCell<Node>> {
+    let tree = Node::new("/");
+    let mut current_node = tree.clone();
+    for x in input.iter() {
+        match x {
+            Command::ChangeDirectory(n) => {
+                if n == "/" {
+                    current_node = tree.clone();
+                } else if n == ".." {
+                    // Going up from / leaves you there, as in a shell
+                    let new_current = current_node.borrow().parent.upgrade().unwrap_or_else(|| tree.clone());
+                    current_node = new_current;
+                } else {
+                    let new_node = Node::new_with_parent(n, Rc::downgrade(&current_node));
+                    current_node.borrow_mut().dirs.push(new_node.clone());
+                    current_node = new_node;
+                }
+            },
+            Command::List(dl) => {
+                current_node.borrow_mut().files.extend(dl.clone());
+            },
+        }
+    }
+
+    tree
+}
+
+fn calc_sizes(tree: &Rc<RefCell<Node>>) -> usize {
+    let mut total_size = tree.borrow().files.iter()
+        .map(|f| match f {
+            DirEntry::File(fd) => fd.size,
+            _ => 0,
+        })
+        .sum::<usize>();
+
+    total_size += tree.borrow().dirs.iter()
+        .map(calc_sizes)
+        .sum::<usize>();
+
+    tree.borrow_mut().total_size = total_size;
+    total_size
+}
+
+// Dumps the tree (with sizes, once calc_sizes has run) at trace level
+fn trace_tree(tree: &Rc<RefCell<Node>>, indent: usize) {
+    let tabs = " ".repeat(indent);
+    let node = tree.borrow();
+    trace!("{}{}:{}", tabs, node.name, node.total_size);
+
+    for f in node.files.iter() {
+        match f {
+            DirEntry::Dir(name) => trace!("{}  dir {}", tabs, name),
+            DirEntry::File(fd) => trace!("{}  {} {}", tabs, fd.size, fd.name),
+        }
+    }
+
+    for d in node.dirs.iter() {
+        trace_tree(d, indent+1);
+    }
+}
+
+fn part_1_impl(node: &Node) -> usize {
+    let mut me = 0;
+    if node.total_size <= 100000 {
+        me = node.total_size;
+        debug!(">> {} has total size {} <= 100000", node.name, node.total_size);
+    }
+
+    node.dirs.iter()
+        .map(|d| part_1_impl(&d.borrow()))
+        .sum::<usize>() + me
+}
+
+pub fn part_1(input: &Input, _: &Config) -> Answer {
+    let tree = build_tree(input);
+    calc_sizes(&tree);
+    if enabled!(Level::TRACE) {
+        trace_tree(&tree, 0);
+    }
+    let res = part_1_impl(&tree.borrow());
+    res.into()
+}
+
+fn part_2_impl(node: &Node, needed_space: usize, min_size: usize) -> usize {
+    if node.total_size < needed_space {
+        // No point continuing
+        return min_size;
+    }
+
+    let mut min_size = min_size;
+    if node.total_size < min_size {
+        debug!("Switching min {}-->{} ({})", min_size, node.total_size, node.name);
+        min_size = node.total_size;
+    }
+
+    node.dirs.iter()
+        .map(|d| part_2_impl(&d.borrow(), needed_space, min_size))
+        .chain(once(min_size))
+        .min()
+        .unwrap()
+}
+
+pub fn part_2(input: &Input, config: &Config) -> Answer {
+    let tree = build_tree(input);
+    calc_sizes(&tree);
+
+    // The config can't know how much the input has on the disk
+    let used = tree.borrow().total_size;
+    let Some(free_space) = config.disk_size.checked_sub(used) else {
+        return format!("More used ({}) than the disk holds!", used).into();
+    };
+    let needed_space = config.needed_size.saturating_sub(free_space);
+
+    info!("Disk size: {}, Free space: {}, Needed space: {}", config.disk_size, free_space, needed_space);
+    let res = part_2_impl(&tree.borrow(), needed_space, usize::MAX);
+    match res {
+        usize::MAX => "Not found!".into(),
+        res => res.into(),
+    }
+}
+
+
+/// Both parts in one pass over the input as it's read. Only the sizes of
+/// the directories on the way to the current one are kept, and the total
+/// size of each directory once it's been left: nothing about files.
+pub fn stream<R: BufRead>(reader: R, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve_streaming(part, || {
+        // Sizes so far of / and the directories down to the current one
+        let mut path = vec![0];
+        let mut sizes = Vec::new();
+        let mut checker = Checker::default();
+        let leave = |path: &mut Vec<usize>, sizes: &mut Vec<usize>| {
+            let size = path.pop().unwrap();
+            *path.last_mut().unwrap() += size;
+            sizes.push(size);
+        };
+
+        for (i, line) in parse::lines(reader).enumerate() {
+            let line = line?;
+            match checker.line(&line).map_err(|e| e.at_line(i + 1))? {
+                Line::Cd(dir) if dir == "/" => {
+                    while path.len() > 1 {
+                        leave(&mut path, &mut sizes);
+                    }
+                },
+                Line::Cd(dir) if dir == ".." => {
+                    if path.len() > 1 {
+                        leave(&mut path, &mut sizes);
+                    }
+                },
+                Line::Cd(_) => path.push(0),
+                Line::Entry(DirEntry::File(fd)) => *path.last_mut().unwrap() += fd.size,
+                Line::Ls | Line::Entry(DirEntry::Dir(_)) => (),
+            }
+        }
+        while path.len() > 1 {
+            leave(&mut path, &mut sizes);
+        }
+        let used = path[0];
+        sizes.push(used);
+
+        let small = sizes.iter().filter(|&&s| s <= 100000).sum::<usize>();
+        let smallest = match config.disk_size.checked_sub(used) {
+            Some(free_space) => {
+                let needed_space = config.needed_size.saturating_sub(free_space);
+                match sizes.iter().filter(|&&s| s >= needed_space).min() {
+                    Some(&s) => s.into(),
+                    None => "Not found!".into(),
+                }
+            },
+            None => format!("More used ({}) than the disk holds!", used).into(),
+        };
+        Ok([small.into(), smallest])
+    })
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+/// The filesystem, for `aoc repl`: `tree` lists a directory and `du` gives
+/// the sizes of the directories in it
+pub struct Explorer {
+    root: Rc<RefCell<Node>>,
+}
+
+impl Explorer {
+    pub fn new(input: &Input) -> Explorer {
+        let root = build_tree(input);
+        calc_sizes(&root);
+        Explorer { root }
+    }
+
+    fn find(&self, path: &str) -> Result<Rc<RefCell<Node>>, String> {
+        let mut node = self.root.clone();
+        for name in path.split('/').filter(|n| !n.is_empty()) {
+            let next = node.borrow().dirs.iter()
+                .find(|d| d.borrow().name == name)
+                .cloned()
+                .ok_or_else(|| format!("No directory {}", path))?;
+            node = next;
+        }
+
+        Ok(node)
+    }
+}
+
+// Listed the way the puzzle does
+fn tree(node: &Node, indent: usize, out: &mut Vec<String>) {
+    let tabs = "  ".repeat(indent);
+    out.push(format!("{}- {} (dir, size={})", tabs, node.name, node.total_size));
+    for d in node.dirs.iter() {
+        tree(&d.borrow(), indent + 1, out);
+    }
+    for f in node.files.iter() {
+        if let DirEntry::File(fd) = f {
+            out.push(format!("{}  - {} (file, size={})", tabs, fd.name, fd.size));
+        }
+    }
+}
+
+// Like du: subdirectories before the directory they're in
+fn du(node: &Node, path: &str, out: &mut Vec<String>) {
+    for d in node.dirs.iter() {
+        let d = d.borrow();
+        du(&d, &format!("{}/{}", path.trim_end_matches('/'), d.name), out);
+    }
+    out.push(format!("{}\t{}", node.total_size, path));
+}
+
+impl Explore for Explorer {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("tree [PATH]", "what's in a directory (/ if left out), with sizes"),
+            ("du [PATH]", "total size of a directory and each one in it"),
+        ]
+    }
+
+    fn command(&mut self, words: &[&str]) -> Result<String, String> {
+        let path = words.get(1).copied().unwrap_or("/");
+        let node = self.find(path)?;
+        let mut out = Vec::new();
+        match words[0] {
+            "tree" => tree(&node.borrow(), 0, &mut out),
+            "du" => du(&node.borrow(), path, &mut out),
+            _ => return Err(format!("Unknown command {:?}", words[0])),
+        }
+
+        Ok(out.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("$ cd /\n$ ls\ndir a\n14848514 b.txt\nb.txt 14848514\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(),
+                   "input.txt:5:1: expected a command or a `dir NAME`/`SIZE NAME` entry, found \"b.txt 14848514\"");
+        assert_eq!(parse("$ cd /\n$ lsd\n").unwrap_err().line, 2);
+        assert_eq!(parse("x$ cd /\n$ ls\n").unwrap_err().line, 1);
+        assert_eq!(parse("$ cd /\n$ ls\nfoo dir a\n").unwrap_err().line, 3);
+
+        // Entries outside a listing
+        let e = parse("$ ls\n5 b\n$ cd x\n7 c\n").unwrap_err();
+        assert_eq!(e.to_string(), "line 4, column 1: expected a command (entries only follow `$ ls`), found \"7 c\"");
+        assert_eq!(parse("$ cd /\n10 a\n").unwrap_err().line, 2);
+        assert_eq!(parse("dir a\n$ ls\n").unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &Config::default()), Answer::Int(95437));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input, &Config::default()), Answer::Int(24933642));
+    }
+
+    #[test]
+    fn explore() {
+        let mut explorer = Explorer::new(&parse(EXAMPLE).unwrap());
+        assert_eq!(explorer.command(&["tree", "/a/e"]), Ok("- e (dir, size=584)\n  - i (file, size=584)".to_string()));
+        assert_eq!(explorer.command(&["du"]), Ok("584\t/a/e\n94853\t/a\n24933642\t/d\n48381165\t/".to_string()));
+        assert!(explorer.command(&["tree", "/a/x"]).is_err());
+    }
+
+    #[test]
+    fn stream_example() {
+        let run = stream(EXAMPLE.as_bytes(), &Config::default(), Part::All).unwrap();
+        let answers = run.results.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers, [Answer::Int(95437), Answer::Int(24933642)]);
+    }
+}
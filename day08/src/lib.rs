@@ -0,0 +1,254 @@
+use std::io::prelude::*;
+use std::iter;
+use tracing::{debug, trace};
+use common::geometry::{Dir4, Point2};
+use common::raster::{gradient, Image, Rgb};
+use common::explore::{arg, Explore};
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+pub struct Tree {
+    height: usize,
+    visible: bool,
+}
+
+
+impl Tree {
+    fn new(height: usize) -> Tree {
+        Tree { height, visible: false }
+    }
+}
+
+
+type Grid = common::grid::Grid<Tree>;
+
+pub type Input = Grid;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    Grid::parse(parse::lines(reader).collect::<Result<Vec<_>, _>>()?, |c, column| {
+        let height = c.to_digit(10)
+            .ok_or_else(|| ParseError::new(column, "a digit", c.to_string()))?;
+        Ok(Tree::new(height as usize))
+    })
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// Mark every tree that can be seen from outside the grid
+fn mark_visible(grid: &mut Grid) {
+    let (cols, rows) = (grid.width(), grid.height());
+
+    // Each line of sight into the grid: the edge tree it starts at and the
+    // direction it looks in
+    let lines_of_sight = (0..cols)
+        .flat_map(|c| [(Point2::new(c, 0), Dir4::Down), (Point2::new(c, rows - 1), Dir4::Up)])
+        .chain((0..rows)
+            .flat_map(|r| [(Point2::new(0, r), Dir4::Right), (Point2::new(cols - 1, r), Dir4::Left)]));
+
+    for (start, dir) in lines_of_sight {
+        let mut max_height = -1;
+        for p in iter::once(start).chain(grid.ray(start, dir.vec())) {
+            if grid[p].height as isize > max_height {
+                max_height = grid[p].height as isize;
+                grid[p].visible = true;
+            }
+        }
+    }
+}
+
+// The grid with hidden trees blanked out
+fn visible_rows(grid: &Grid) -> impl Iterator<Item = String> + '_ {
+    grid.rows().map(|row| row.iter()
+        .map(|t| match t.visible {
+            true => char::from_digit(t.height as u32, 10).unwrap(),
+            false => ' ',
+        })
+        .collect())
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    let mut grid = input.clone();
+    mark_visible(&mut grid);
+
+    for row in visible_rows(&grid) {
+        trace!("{}", row);
+    }
+
+    grid.iter().filter(|t| t.visible).count().into()
+}
+
+pub fn part_2(grid: &Input) -> Answer {
+    let mut max_scenic_score = 0;
+    for p in grid.points() {
+        let s = calc_scenic_score(grid, p);
+        if s > max_scenic_score {
+            debug!("Shifting {}-->{} ({},{})", max_scenic_score, s, p.x, p.y);
+            max_scenic_score = s;
+        }
+    }
+
+    max_scenic_score.into()
+}
+
+// Trees seen from `p` looking in `dir`, up to and including the first one
+// that is at least as tall
+fn viewing_distance(grid: &Grid, p: Point2<usize>, dir: Dir4) -> usize {
+    let h = grid[p].height;
+    let mut count = 0;
+    for t in grid.ray(p, dir.vec()) {
+        count += 1;
+        if grid[t].height >= h {
+            break;
+        }
+    }
+
+    count
+}
+
+fn calc_scenic_score(grid: &Grid, p: Point2<usize>) -> usize {
+    Dir4::ALL.iter()
+        .map(|d| viewing_distance(grid, p, *d))
+        .product()
+}
+
+/// Every tree, shaded by height: in greens if it can be seen from outside
+/// the grid, greys if it's hidden
+pub fn render(input: &Input) -> Image {
+    let mut grid = input.clone();
+    mark_visible(&mut grid);
+
+    let mut palette = gradient(Rgb(40, 40, 40), Rgb(150, 150, 150), 10);
+    palette.extend(gradient(Rgb(0, 80, 0), Rgb(120, 255, 120), 10));
+
+    let pixels = grid.iter()
+        .map(|t| t.height.min(9) as u8 + if t.visible { 10 } else { 0 })
+        .collect();
+    Image::new(common::grid::Grid::from_vec(grid.width(), pixels), &palette).scaled(4)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+/// The trees, for `aoc repl`: `score` looks at one of them and `visible`
+/// shows the ones that can be seen from outside
+pub struct Explorer {
+    grid: Grid,
+}
+
+impl Explorer {
+    pub fn new(input: &Input) -> Explorer {
+        let mut grid = input.clone();
+        mark_visible(&mut grid);
+        Explorer { grid }
+    }
+}
+
+impl Explore for Explorer {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("score X Y", "height, visibility and scenic score of a tree"),
+            ("visible", "the trees that can be seen from outside the grid"),
+        ]
+    }
+
+    fn command(&mut self, words: &[&str]) -> Result<String, String> {
+        match words[0] {
+            "score" => {
+                let p = Point2::new(arg(words, 1, None)?, arg(words, 2, None)?);
+                let tree = self.grid.get(p).ok_or_else(|| format!("No tree at {},{}", p.x, p.y))?;
+                Ok(format!("Height {}, {}, scenic score {}",
+                           tree.height,
+                           if tree.visible { "visible" } else { "hidden" },
+                           calc_scenic_score(&self.grid, p)))
+            },
+            "visible" => Ok(visible_rows(&self.grid).collect::<Vec<_>>().join("\n")),
+            _ => Err(format!("Unknown command {:?}", words[0])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn render_example() {
+        let image = render(&parse(EXAMPLE).unwrap());
+        assert_eq!((image.pixels.width(), image.pixels.height()), (20, 20));
+
+        // Visible trees are the second half of the palette
+        let visible = image.pixels.iter().filter(|&&i| i >= 10).count();
+        assert_eq!(visible, 21 * 4 * 4);
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(21));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(8));
+    }
+
+    fn grid() -> impl Strategy<Value = Grid> {
+        (1..8usize, 1..8usize).prop_flat_map(|(cols, rows)| {
+            prop::collection::vec(0..10usize, cols * rows)
+                .prop_map(move |heights| Grid::from_vec(cols, heights.into_iter().map(Tree::new).collect()))
+        })
+    }
+
+    // Visible if every tree between it and some edge is shorter
+    fn visible(grid: &Grid, p: Point2<usize>) -> bool {
+        Dir4::ALL.iter()
+            .any(|d| grid.ray(p, d.vec()).all(|t| grid[t].height < grid[p].height))
+    }
+
+    proptest! {
+        #[test]
+        fn visibility_matches_brute_force(grid in grid()) {
+            let mut marked = grid.clone();
+            mark_visible(&mut marked);
+            for p in grid.points() {
+                prop_assert_eq!(marked[p].visible, visible(&grid, p), "at {:?}", p);
+            }
+        }
+    }
+
+    #[test]
+    fn explore() {
+        let mut explorer = Explorer::new(&parse(EXAMPLE).unwrap());
+        assert_eq!(explorer.command(&["score", "2", "3"]), Ok("Height 5, visible, scenic score 8".to_string()));
+        assert_eq!(explorer.command(&["score", "1", "3"]), Ok("Height 3, hidden, scenic score 1".to_string()));
+        assert!(explorer.command(&["score", "5", "0"]).is_err());
+    }
+}
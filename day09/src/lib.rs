@@ -0,0 +1,284 @@
+use std::io::prelude::*;
+use std::collections::HashSet;
+use std::iter;
+use tracing::trace;
+use common::anim::Frame;
+use common::geometry::{Dir4, Point2, Vec2};
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+pub struct Action {
+    vector: Vec2,
+    count: usize,
+}
+
+// Real moves are a few dozen steps at most. This keeps the rope's positions
+// from overflowing however many moves there are.
+const MAX_STEPS: usize = 100000;
+
+impl Action {
+    fn new(line: &str) -> Result<Action, ParseError> {
+        let line = line.trim_end();
+        let (dir, count) = line.split_once(' ')
+            .ok_or_else(|| ParseError::new(line.len() + 1, "a step count", ""))?;
+        let column = parse::column_of(line, count);
+        let count = match parse::number::<isize>(count, column)? {
+            n if (1..=MAX_STEPS as isize).contains(&n) => n as usize,
+            _ => return Err(ParseError::new(column, format!("a step count from 1 to {}", MAX_STEPS), count)),
+        };
+        match dir {
+            "U"=> Ok(Action{ vector: Dir4::Up.vec(), count }),
+            "D"=> Ok(Action{ vector: Dir4::Down.vec(), count }),
+            "L"=> Ok(Action{ vector: Dir4::Left.vec(), count }),
+            "R"=> Ok(Action{ vector: Dir4::Right.vec(), count }),
+            _=> Err(ParseError::new(1, "U, D, L or R", dir)),
+        }
+    }
+}
+
+// How a knot moves after the one ahead of it (`head`) did. It only moves
+// once they're no longer touching, and then it moves (diagonally if need be)
+// one step towards it.
+fn pull(head: Point2<isize>, tail: Point2<isize>) -> Vec2 {
+    let dist = head - tail;
+    if dist.chebyshev() > 1 {
+        dist.signum()
+    } else {
+        Vec2::ZERO
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Rope {
+    head: Point2<isize>,
+    tail: Point2<isize>,
+}
+
+impl Rope {
+    fn new() -> Rope {
+        Rope { head: Point2::default(), tail: Point2::default() }
+    }
+
+    // Adds where the tail goes to `visited`
+    fn process(&mut self, action: &Action, visited: &mut HashSet<Point2<isize>>) {
+        for _ in 0..action.count {
+            visited.insert(self._process_single(action).0);
+        }
+    }
+
+    fn process2(&mut self, action: &Action) -> Vec<Action> {
+        (0..action.count)
+            .map(|_| self._process_single(action).1)
+            .map(|v| Action { vector: v, count: 1 })
+            .collect()
+    }
+
+    fn _get_dir(&mut self, action: &Action) -> Vec2 {
+        self.head += action.vector;
+        pull(self.head, self.tail)
+    }
+
+    fn _process_single(&mut self, action: &Action) -> (Point2<isize>, Vec2) {
+        let dir = self._get_dir(action);
+
+        self.tail += dir;
+        (self.tail, dir)
+    }
+}
+
+pub type Input = Vec<Action>;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| Action::new(&l).map_err(|e| e.at_line(i + 1))))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    let mut rope = Rope::new();
+    let mut visited = HashSet::from([Point2::default()]);
+    for action in input.iter() {
+        rope.process(action, &mut visited);
+    }
+
+    visited.len().into()
+}
+
+
+pub fn part_2(input: &Input) -> Answer {
+    let mut ropes = vec![Rope::new(); 9];
+    let mut visited = HashSet::from([Point2::default()]);
+
+    for action in input.iter() {
+        trace!(?action);
+        let mut actions = ropes[0].process2(action);
+        for rope in ropes[1..8].iter_mut() {
+            actions = actions.iter()
+                .flat_map(|a| rope.process2(a))
+                .collect();
+        }
+
+        for a in actions.iter() {
+            ropes[8].process(a, &mut visited);
+        }
+    }
+
+    visited.len().into()
+}
+
+fn knot_char(i: usize, knots: usize) -> char {
+    match i {
+        0 => 'H',
+        1 if knots == 2 => 'T',
+        i => char::from_digit(i as u32, 36).unwrap(),
+    }
+}
+
+/// A rope of `knots` knots (2 in part 1, 10 in part 2) moving a step at a
+/// time, leaving a trail where its tail has been
+pub fn steps(input: &Input, knots: usize) -> impl Iterator<Item = Frame> + '_ {
+    let mut rope = vec![Point2::default(); knots];
+    let mut visited = HashSet::from([Point2::default()]);
+
+    let mut start = Frame::new(Point2::default(), "Start");
+    start.set(Point2::default(), 'H');
+
+    let moves = input.iter()
+        .enumerate()
+        .flat_map(|(i, a)| iter::repeat_n((i, a), a.count));
+
+    iter::once(start).chain(moves.map(move |(i, a)| {
+        let mut frame = Frame::new(Point2::default(), "");
+
+        // Uncover where the rope was
+        for p in rope.iter() {
+            frame.set(*p, if visited.contains(p) { '#' } else { ' ' });
+        }
+
+        rope[0] += a.vector;
+        for k in 1..rope.len() {
+            let d = pull(rope[k - 1], rope[k]);
+            rope[k] += d;
+        }
+        visited.insert(*rope.last().unwrap());
+
+        // Tail first, so knots nearer the head are drawn over it
+        for (k, p) in rope.iter().enumerate().rev() {
+            frame.set(*p, knot_char(k, knots));
+        }
+
+        frame.focus = rope[0];
+        frame.caption = format!("Move {} of {}: the tail has visited {} positions", i + 1, input.len(), visited.len());
+        frame
+    }))
+}
+
+/// Both parts in one pass over the input as it's read. Knot 1 of part 2's
+/// rope moves just like part 1's tail, so one rope does for both. Only the
+/// positions the tails visit are kept, not the moves.
+pub fn stream<R: BufRead>(reader: R, part: Part) -> Result<Run, ParseError> {
+    common::solve_streaming(part, || {
+        let mut rope = [Point2::default(); 10];
+        let mut visited = [HashSet::from([Point2::default()]), HashSet::from([Point2::default()])];
+
+        for (i, l) in parse::lines(reader).enumerate() {
+            let l = l?;
+            let action = Action::new(&l).map_err(|e| e.at_line(i + 1))?;
+            for _ in 0..action.count {
+                rope[0] += action.vector;
+                for k in 1..rope.len() {
+                    let d = pull(rope[k - 1], rope[k]);
+                    rope[k] += d;
+                }
+                visited[0].insert(rope[1]);
+                visited[1].insert(rope[9]);
+            }
+        }
+
+        Ok(visited.map(|v| v.len().into()))
+    })
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+    // The longer rope only moves its tail on the larger example
+    const LARGER_EXAMPLE: &str = include_str!("example2.txt");
+
+    #[test]
+    fn parse_error() {
+        for count in ["0", "-3", "9223372036854775807"] {
+            let e = parse(&format!("U 1\nR {}\n", count)).unwrap_err();
+            assert_eq!((e.line, e.column), (2, 3));
+            assert_eq!(e.found, count);
+        }
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(13));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(1));
+    }
+
+    #[test]
+    fn part_2_larger_example() {
+        let input = read_input(LARGER_EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(36));
+    }
+
+    #[test]
+    fn steps_end_like_the_parts() {
+        let input = parse(EXAMPLE).unwrap();
+        let last = steps(&input, 2).last().unwrap();
+        assert!(last.caption.ends_with("visited 13 positions"), "{}", last.caption);
+
+        let input = parse(LARGER_EXAMPLE).unwrap();
+        let last = steps(&input, 10).last().unwrap();
+        assert!(last.caption.ends_with("visited 36 positions"), "{}", last.caption);
+    }
+
+    #[test]
+    fn stream_example() {
+        let run = stream(EXAMPLE.as_bytes(), Part::All).unwrap();
+        let answers = run.results.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers, [Answer::Int(13), Answer::Int(1)]);
+    }
+}
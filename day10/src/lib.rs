@@ -0,0 +1,364 @@
+use std::io::prelude::*;
+use std::collections::HashSet;
+use tracing::trace;
+use common::anim::Frame;
+use common::geometry::Point2;
+use common::grid::Grid;
+use common::raster::{Image, Rgb};
+use serde::Deserialize;
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+pub enum Instruction {
+    Noop,
+    AddX(isize),
+}
+
+// Real programs add a few dozen at most. This keeps X from overflowing
+// however long the program gets.
+const MAX_ADDX: isize = 1000;
+
+fn operand(s: &str, column: usize) -> Result<isize, ParseError> {
+    match parse::number(s, column)? {
+        n if (-MAX_ADDX..=MAX_ADDX).contains(&n) => Ok(n),
+        _ => Err(ParseError::new(column, format!("a number from -{} to {}", MAX_ADDX, MAX_ADDX), s)),
+    }
+}
+
+impl Instruction {
+    fn from_input_line(l: &str) -> Result<Instruction, ParseError> {
+        let l = l.trim_end();
+        if l == "noop" {
+            return Ok(Instruction::Noop);
+        }
+
+        match l.split_once(' ') {
+            Some(("addx", x)) => Ok(Instruction::AddX(operand(x, parse::column_of(l, x))?)),
+            _ => Err(ParseError::new(1, "noop or addx N", l)),
+        }
+    }
+}
+
+struct CpuState {
+    x: isize,
+    time: usize,
+    history: Vec<isize>,
+}
+
+impl CpuState {
+    fn new() -> CpuState {
+        CpuState {x: 1, time: 1, history: vec![]}
+    }
+
+    // Nothing once the program's finished, and None if it's too strong to
+    // hold
+    fn get_signal_strength(&self, time: usize) -> Option<isize> {
+        self.history.get(time - 1).map_or(Some(0), |&x| signal_strength(x, time))
+    }
+
+    fn _state(&self) -> String {
+        format!("time,x,hist_len {},{},{}",
+                self.time,
+                self.x,
+                self.history.len())
+    }
+
+    fn execute(&mut self, i: &Instruction) {
+        match i {
+            Instruction::Noop => {
+                self.history.push(self.x);
+                self.time += 1;
+            },
+            Instruction::AddX(x) => {
+                self.history.push(self.x);
+                self.history.push(self.x);
+                self.time += 2;
+                self.x += x;
+            }
+        }
+    }
+}
+
+// X during cycle `t`, multiplied by `t`
+fn signal_strength(x: isize, t: usize) -> Option<isize> {
+    isize::try_from(t).ok().and_then(|t| x.checked_mul(t))
+}
+
+const COLS: usize = 40;
+const ROWS: usize = 6;
+
+// Whether the sprite (3 pixels wide, centred on `sprite_loc`) covers `x`
+fn lit(x: isize, sprite_loc: isize) -> bool {
+    x >= sprite_loc-1 && x <= sprite_loc+1
+}
+
+struct Display {
+    pos: usize,
+    pixels: Grid<char>,
+}
+
+impl Display {
+    fn new() -> Display {
+        Display { pos: 0, pixels: Grid::new(COLS, ROWS, '.') }
+    }
+
+    fn draw(&mut self, sprite_loc: isize) {
+        let p = Point2::new(self.pos % COLS, self.pos / COLS);
+        if lit(p.x as isize, sprite_loc) {
+            self.pixels[p] = '#';
+        }
+
+        self.pos += 1;
+    }
+
+    fn render(&self) -> String {
+        self.pixels.to_string()
+    }
+}
+
+pub type Input = Vec<Instruction>;
+
+/// The cycles part 1 adds the signal strength of
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub signal_cycles: Vec<usize>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { signal_cycles: vec![20, 60, 100, 140, 180, 220] }
+    }
+}
+
+// Cycles count from 1, and each is counted once
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.signal_cycles.contains(&0) {
+            return Err("signal_cycles must all be at least 1".to_string());
+        }
+
+        let mut seen = HashSet::new();
+        if let Some(c) = self.signal_cycles.iter().find(|&&c| !seen.insert(c)) {
+            return Err(format!("signal_cycles has {} more than once", c));
+        }
+
+        Ok(())
+    }
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| Instruction::from_input_line(&l).map_err(|e| e.at_line(i + 1))))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+fn run_program(input: &Input) -> CpuState {
+    let mut cpu = CpuState::new();
+    for i in input.iter() {
+        cpu.execute(i);
+        trace!(instruction = ?i, cpu.time, cpu.x);
+    }
+
+    cpu
+}
+
+pub fn part_1(input: &Input, config: &Config) -> Answer {
+    let cpu = run_program(input);
+    let total = config.signal_cycles.iter()
+        .try_fold(0isize, |acc, &c| cpu.get_signal_strength(c).and_then(|s| acc.checked_add(s)));
+    match total {
+        Some(total) => total.into(),
+        None => "Signal too strong!".into(),
+    }
+}
+
+pub fn part_2(input: &Input, _: &Config) -> Answer {
+    let cpu = run_program(input);
+    let mut display = Display::new();
+    for t in 1..=(COLS * ROWS).min(cpu.history.len()) {
+        display.draw(cpu.history[t-1]);
+    }
+
+    Answer::Image(display.render())
+}
+
+/// The CRT's screen once the program has run, a pixel a square
+pub fn render(input: &Input) -> Image {
+    let cpu = run_program(input);
+    let mut display = Display::new();
+    for t in 1..=(COLS * ROWS).min(cpu.history.len()) {
+        display.draw(cpu.history[t-1]);
+    }
+
+    let pixels = display.pixels.iter().map(|&c| (c == '#') as u8).collect();
+    Image::new(Grid::from_vec(COLS, pixels), &[Rgb(15, 15, 35), Rgb(255, 255, 100)]).scaled(10)
+}
+
+/// The CRT drawing a pixel each cycle, with the sprite shown on a line of
+/// its own below the screen
+pub fn steps(input: &Input, config: &Config) -> impl Iterator<Item = Frame> {
+    let history = run_program(input).history;
+    let signal_cycles = config.signal_cycles.clone();
+    let mut strength = 0;
+
+    (1..=history.len().min(COLS * ROWS)).map(move |t| {
+        let x = history[t - 1];
+        if signal_cycles.contains(&t) {
+            strength += x * t as isize;
+        }
+
+        let p = Point2::new(((t - 1) % COLS) as isize, ((t - 1) / COLS) as isize);
+        let mut frame = Frame::new(p, format!("Cycle {}: X = {}, signal strength so far {}", t, x, strength));
+        frame.set(p, if lit(p.x, x) { '#' } else { '.' });
+
+        let sprite = (0..COLS as isize)
+            .map(|col| if lit(col, x) { '#' } else { '.' })
+            .collect::<String>();
+        frame.text(Point2::new(0, ROWS as isize + 1), &sprite);
+        frame
+    })
+}
+
+/// Both parts in one pass over the program as it's read, keeping nothing
+/// but the screen
+pub fn stream<R: BufRead>(reader: R, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve_streaming(part, || {
+        let (mut x, mut t) = (1, 0);
+        let mut strength = Some(0isize);
+        let mut display = Display::new();
+        let mut tick = |x: isize| {
+            t += 1;
+            if config.signal_cycles.contains(&t) {
+                strength = strength.and_then(|s| s.checked_add(signal_strength(x, t)?));
+            }
+            if t <= COLS * ROWS {
+                display.draw(x);
+            }
+        };
+
+        for (i, l) in parse::lines(reader).enumerate() {
+            let l = l?;
+            match Instruction::from_input_line(&l).map_err(|e| e.at_line(i + 1))? {
+                Instruction::Noop => tick(x),
+                Instruction::AddX(v) => {
+                    tick(x);
+                    tick(x);
+                    x += v;
+                },
+            }
+        }
+
+        let strength = match strength {
+            Some(strength) => strength.into(),
+            None => "Signal too strong!".into(),
+        };
+        Ok([strength, Answer::Image(display.render())])
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::anim::{Canvas, Viewport};
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("noop\naddx 1y\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:2:6: expected a number, found \"1y\"");
+        assert_eq!(parse("mul 2").unwrap_err().column, 1);
+        let e = parse("addx 9223372036854775807\naddx 1\n").unwrap_err();
+        assert_eq!((e.line, e.column), (1, 6));
+    }
+
+    #[test]
+    fn config_error() {
+        assert!(Config { signal_cycles: vec![20, 0] }.validate().is_err());
+        assert_eq!(Config { signal_cycles: vec![2, 3, 2] }.validate().unwrap_err(), "signal_cycles has 2 more than once");
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &Config::default()), Answer::Int(13140));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        let expected = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+        assert_eq!(part_2(&input, &Config::default()), Answer::Image(expected.to_string()));
+    }
+
+    #[test]
+    fn render_example() {
+        let image = render(&parse(EXAMPLE).unwrap());
+        assert_eq!((image.pixels.width(), image.pixels.height()), (400, 60));
+        // The first row starts ##..
+        let row = image.pixels.row(0).step_by(10).take(4).copied().collect::<Vec<_>>();
+        assert_eq!(row, [1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn steps_draw_the_image() {
+        let input = parse(EXAMPLE).unwrap();
+        let mut canvas = Canvas::new();
+        let mut last = None;
+        for frame in steps(&input, &Config::default()) {
+            canvas.apply(&frame);
+            last = Some(frame);
+        }
+
+        let screen = Viewport { origin: Point2::new(0, 0), width: COLS, height: ROWS };
+        assert_eq!(Answer::Image(canvas.render(&screen)), part_2(&input, &Config::default()));
+        assert!(last.unwrap().caption.ends_with("signal strength so far 13140"));
+    }
+
+    #[test]
+    fn stream_example() {
+        let input = parse(EXAMPLE).unwrap();
+        let config = Config::default();
+        let run = stream(EXAMPLE.as_bytes(), &config, Part::All).unwrap();
+        let answers = run.results.iter().map(|r| r.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(answers, [part_1(&input, &config), part_2(&input, &config)]);
+    }
+}
@@ -0,0 +1,474 @@
+use std::io::prelude::*;
+use regex::Regex;
+use lazy_static::lazy_static;
+use tracing::trace;
+use serde::Deserialize;
+use common::explore::{arg, Explore};
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+struct ItemTest {
+    divisor: usize,
+    true_action: usize,
+    false_action: usize,
+}
+
+#[derive(Debug, Clone)]
+enum Operation {
+    Add(usize),
+    Mult(usize),
+    Square,
+}
+
+
+impl Operation {
+    fn from_line(l: &str) -> Result<Operation, ParseError> {
+        lazy_static! {
+            static ref ADD_RE: Regex = Regex::new(r"^\s*Operation: new = old \+ (\d+)$").unwrap();
+            static ref MULT_RE: Regex = Regex::new(r"^\s*Operation: new = old \* (\d+)$").unwrap();
+            static ref SQUARE_RE: Regex = Regex::new(r"^\s*Operation: new = old \* old$").unwrap();
+        }
+
+        let num = |c: regex::Captures| {
+            let m = c.get(1).unwrap();
+            parse::number::<usize>(m.as_str(), m.start() + 1)
+        };
+
+        if let Some(c) = ADD_RE.captures(l) {
+            Ok(Operation::Add(num(c)?))
+        } else if let Some(c) = MULT_RE.captures(l) {
+            Ok(Operation::Mult(num(c)?))
+        } else if SQUARE_RE.is_match(l) {
+            Ok(Operation::Square)
+        } else {
+            Err(ParseError::new(1, "Operation: new = old + N, old * N or old * old", l))
+        }
+    }
+
+    // Wide enough that it can't overflow, whatever the usizes
+    fn eval(&self, i: usize) -> u128 {
+        let i = i as u128;
+        match self {
+            Operation::Add(x) => i + *x as u128,
+            Operation::Mult(x) => i * *x as u128,
+            Operation::Square => i * i,
+        }
+    }
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<usize>,
+    op: Operation,
+    test: ItemTest,
+    inspected: usize,
+}
+
+impl Monkey {
+    // Return a vector of (worry_level, target_monkey), or None if a worry
+    // level got too big for a usize
+    fn play_turn(&mut self, relief: usize) -> Option<Vec<(usize, usize)>> {
+        self.inspected += self.items.len();
+        self.items.drain(0..)
+            .map(|i| usize::try_from(self.op.eval(i) / relief as u128).ok())
+            .map(|i| i.map(|i| if i % self.test.divisor == 0 { (i, self.test.true_action)} else {(i, self.test.false_action)}))
+            .collect()
+    }
+
+    fn play_turn2(&mut self, reducer: usize) -> Vec<(usize, usize)> {
+        self.inspected += self.items.len();
+        self.items.drain(0..)
+            .map(|i| (self.op.eval(i) % reducer as u128) as usize)
+            .map(|i| if i % self.test.divisor == 0 { (i, self.test.true_action)} else {(i, self.test.false_action)})
+            .collect()
+    }
+}
+
+pub type Input = Vec<Monkey>;
+
+/// How many rounds each part plays, and what worry levels are divided by
+/// after each inspection in part 1
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub rounds_1: usize,
+    pub rounds_2: usize,
+    pub relief: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { rounds_1: 20, rounds_2: 10000, relief: 3 }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.relief == 0 {
+            return Err("relief must be at least 1".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_items(l: &str) -> Result<Vec<usize>, ParseError> {
+    lazy_static! {
+        static ref ITEMS_RE: Regex = Regex::new(r"^\s*Starting items:(.*)$").unwrap();
+    }
+
+    let items = ITEMS_RE.captures(l)
+        .ok_or_else(|| ParseError::new(1, "Starting items: ...", l))?
+        .get(1).unwrap().as_str();
+
+    if items.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    items.split(',')
+        .map(|n| n.trim())
+        .map(|n| parse::number(n, parse::column_of(l, n)))
+        .collect()
+}
+
+// The number ending one of a monkey's lines, which `re` matches all of
+fn parse_num(l: &str, re: &Regex, expected: &str) -> Result<usize, ParseError> {
+    let m = re.captures(l)
+        .ok_or_else(|| ParseError::new(1, expected, l))?
+        .get(1).unwrap();
+    parse::number(m.as_str(), m.start() + 1)
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    lazy_static! {
+        static ref HEADER_RE: Regex = Regex::new(r"^Monkey \d+:$").unwrap();
+        static ref TEST_RE: Regex = Regex::new(r"^\s*Test: divisible by (\d+)$").unwrap();
+        static ref TRUE_RE: Regex = Regex::new(r"^\s*If true: throw to monkey (\d+)$").unwrap();
+        static ref FALSE_RE: Regex = Regex::new(r"^\s*If false: throw to monkey (\d+)$").unwrap();
+    }
+
+    let lines = parse::lines(reader).collect::<Result<Vec<_>, _>>()?;
+
+    let mut res = vec![];
+    let mut targets = vec![];
+    let mut product = 1usize;
+    let mut i = 0;
+    loop {
+        if i >= lines.len() {
+            break;
+        }
+
+        if lines[i].trim().is_empty() {
+            i += 1;
+            continue;
+        }
+
+        // Each monkey is a block of six lines; the header's number isn't used
+        let line = |j: usize| {
+            lines.get(i + j)
+                .ok_or_else(|| ParseError::new(1, "another line of the monkey definition", "").at_line(i + j + 1))
+        };
+        let at = |j: usize| move |e: ParseError| e.at_line(i + j + 1);
+
+        if !HEADER_RE.is_match(&lines[i]) {
+            return Err(ParseError::new(1, "Monkey N:", lines[i].as_str()).at_line(i + 1));
+        }
+
+        let divisor = parse_num(line(3)?, &TEST_RE, "Test: divisible by N").map_err(at(3))?;
+        if divisor == 0 {
+            return Err(ParseError::new(1, "a non-zero divisor", line(3)?.as_str()).at_line(i + 4));
+        }
+
+        // Part 2 keeps worry levels modulo all the divisors multiplied
+        product = match product.checked_mul(divisor) {
+            Some(p) => p,
+            None => return Err(ParseError::new(1, "a divisor that keeps the product of them all in a usize", line(3)?.as_str()).at_line(i + 4)),
+        };
+
+        res.push(Monkey{
+            items: parse_items(line(1)?).map_err(at(1))?,
+            op: Operation::from_line(line(2)?).map_err(at(2))?,
+            test: ItemTest {
+                divisor,
+                true_action: parse_num(line(4)?, &TRUE_RE, "If true: throw to monkey N").map_err(at(4))?,
+                false_action: parse_num(line(5)?, &FALSE_RE, "If false: throw to monkey N").map_err(at(5))?,
+            },
+            inspected: 0,
+        });
+        targets.push((i + 5, res.last().unwrap().test.true_action));
+        targets.push((i + 6, res.last().unwrap().test.false_action));
+        i += 6;
+    }
+
+    // Items can only be thrown to monkeys that exist
+    if let Some((l, _)) = targets.into_iter().find(|(_, t)| *t >= res.len()) {
+        let found = lines[l - 1].as_str();
+        let start = found.char_indices()
+            .rfind(|(_, c)| !c.is_ascii_digit())
+            .map_or(0, |(j, c)| j + c.len_utf8());
+        return Err(ParseError::new(start + 1, "an existing monkey", &found[start..]).at_line(l));
+    }
+
+    Ok(res)
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// The two busiest monkeys' inspections multiplied
+fn monkey_business(monkeys: &mut [Monkey]) -> Answer {
+    monkeys.sort_by_key(|m| m.inspected);
+    match monkeys.iter().rev().take(2).try_fold(1usize, |acc, m| acc.checked_mul(m.inspected)) {
+        Some(business) => business.into(),
+        None => "Too much monkey business!".into(),
+    }
+}
+
+pub fn part_1(input: &Input, config: &Config) -> Answer {
+    let mut monkeys = input.clone();
+    for round in 1..=config.rounds_1 {
+        for m in 0..monkeys.len() {
+            let Some(thrown) = monkeys[m].play_turn(config.relief) else {
+                return "Worry levels got too big!".into();
+            };
+            for (i, to) in thrown {
+                monkeys[to].items.push(i);
+            }
+        }
+        trace!(round, inspected = ?monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>());
+    }
+
+    monkey_business(&mut monkeys)
+}
+
+
+// Worry levels can be kept modulo this without changing any monkey's test
+fn reducer(monkeys: &[Monkey]) -> usize {
+    monkeys.iter()
+        .map(|m| m.test.divisor)
+        .product::<usize>()
+}
+
+fn play_rounds2(monkeys: &mut [Monkey], rounds: usize, reducer: usize) {
+    for round in 1..=rounds {
+        for m in 0..monkeys.len() {
+            for (i, to) in monkeys[m].play_turn2(reducer) {
+                monkeys[to].items.push(i);
+            }
+        }
+        trace!(round, inspected = ?monkeys.iter().map(|m| m.inspected).collect::<Vec<_>>());
+    }
+}
+
+pub fn part_2(input: &Input, config: &Config) -> Answer {
+    let mut monkeys = input.clone();
+    let reducer = reducer(&monkeys);
+    play_rounds2(&mut monkeys, config.rounds_2, reducer);
+
+    monkey_business(&mut monkeys)
+}
+
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+/// The monkeys, for `aoc repl`, playing by part 1's rules (worry relieved
+/// after each inspection) or part 2's
+pub struct Explorer {
+    monkeys: Vec<Monkey>,
+    relief: usize,
+    reducer: Option<usize>,
+    rounds: usize,
+}
+
+impl Explorer {
+    pub fn new(input: &Input, config: &Config, part_2: bool) -> Explorer {
+        let reducer = part_2.then(|| reducer(input));
+        Explorer { monkeys: input.clone(), relief: config.relief, reducer, rounds: 0 }
+    }
+
+    fn round(&mut self) -> Result<(), String> {
+        for m in 0..self.monkeys.len() {
+            let thrown = match self.reducer {
+                Some(reducer) => self.monkeys[m].play_turn2(reducer),
+                None => self.monkeys[m].play_turn(self.relief)
+                    .ok_or_else(|| format!("Worry levels got too big in round {}", self.rounds + 1))?,
+            };
+            for (i, to) in thrown {
+                self.monkeys[to].items.push(i);
+            }
+        }
+        self.rounds += 1;
+        Ok(())
+    }
+
+    fn describe(&self) -> String {
+        let mut lines = vec![format!("After {} rounds:", self.rounds)];
+        for (n, m) in self.monkeys.iter().enumerate() {
+            let items = m.items.iter().map(|i| format!(" {}", i)).collect::<Vec<_>>();
+            lines.push(format!("Monkey {}:{} (inspected {} items)", n, items.join(","), m.inspected));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Explore for Explorer {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("round [N]", "play N rounds (1 if left out)"),
+            ("monkeys", "what each monkey holds and has inspected"),
+        ]
+    }
+
+    fn command(&mut self, words: &[&str]) -> Result<String, String> {
+        match words[0] {
+            "round" => {
+                for _ in 0..arg(words, 1, Some(1))? {
+                    self.round()?;
+                }
+                Ok(self.describe())
+            },
+            "monkeys" => Ok(self.describe()),
+            _ => Err(format!("Unknown command {:?}", words[0])),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse(&EXAMPLE.replacen("divisible by 23", "divisible by 23 or 5", 1)).unwrap_err();
+        assert_eq!((e.line, e.column, e.expected.as_str()), (4, 1, "Test: divisible by N"));
+
+        let e = parse(&EXAMPLE.replacen("Operation: new = old * 19", "Operation: new = old * 19 + 1", 1)).unwrap_err();
+        assert_eq!((e.line, e.column), (3, 1));
+
+        let e = parse(&EXAMPLE.replacen("If true", "Maybe if true", 1)).unwrap_err();
+        assert_eq!((e.line, e.found.as_str()), (5, "    Maybe if true: throw to monkey 2"));
+
+        let e = parse(&EXAMPLE.replacen("Monkey 1:", "Monkey 1: junk", 1)).unwrap_err();
+        assert_eq!((e.line, e.column), (8, 1));
+        assert_eq!(parse(&EXAMPLE.replacen("  Starting", "x Starting", 1)).unwrap_err().line, 2);
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &Config::default()), Answer::Int(10605));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input, &Config::default()), Answer::Int(2713310158));
+    }
+
+    #[test]
+    fn explore() {
+        let mut explorer = Explorer::new(&parse(EXAMPLE).unwrap(), &Config::default(), false);
+        let after_one = explorer.command(&["round"]).unwrap();
+        assert!(after_one.contains("Monkey 0: 20, 23, 27, 26 (inspected 2 items)"));
+        assert!(explorer.command(&["round", "19"]).unwrap().contains("Monkey 3: (inspected 105 items)"));
+    }
+
+    fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+        let op = prop_oneof![
+            (0..20usize).prop_map(Operation::Add),
+            (1..20usize).prop_map(Operation::Mult),
+            Just(Operation::Square),
+        ];
+        let divisor = prop::sample::select(vec![2usize, 3, 5, 7, 11, 13, 17, 19, 23]);
+
+        (2..6usize).prop_flat_map(move |n| {
+            // Targets are picked among the other monkeys
+            let monkey = (prop::collection::vec(0..100usize, 0..5), op.clone(), divisor.clone(), 0..n - 1, 0..n - 1);
+            prop::collection::vec(monkey, n).prop_map(|ms| {
+                ms.into_iter().enumerate()
+                    .map(|(j, (items, op, divisor, t, f))| Monkey {
+                        items,
+                        op,
+                        test: ItemTest {
+                            divisor,
+                            true_action: if t >= j { t + 1 } else { t },
+                            false_action: if f >= j { f + 1 } else { f },
+                        },
+                        inspected: 0,
+                    })
+                    .collect()
+            })
+        })
+    }
+
+    // One round with exact worry levels, or None once they don't fit in a u128
+    fn exact_round(items: &mut [Vec<u128>], monkeys: &[Monkey], inspected: &mut [usize]) -> Option<()> {
+        for (m, monkey) in monkeys.iter().enumerate() {
+            for i in std::mem::take(&mut items[m]) {
+                inspected[m] += 1;
+                let i = match monkey.op {
+                    Operation::Add(x) => i.checked_add(x as u128)?,
+                    Operation::Mult(x) => i.checked_mul(x as u128)?,
+                    Operation::Square => i.checked_mul(i)?,
+                };
+                let to = match i % monkey.test.divisor as u128 == 0 {
+                    true => monkey.test.true_action,
+                    false => monkey.test.false_action,
+                };
+                items[to].push(i);
+            }
+        }
+
+        Some(())
+    }
+
+    proptest! {
+        #[test]
+        fn reducer_matches_exact_worry(monkeys in monkeys()) {
+            let reducer = reducer(&monkeys);
+            let mut reduced = monkeys.clone();
+            let mut items = monkeys.iter()
+                .map(|m| m.items.iter().map(|i| *i as u128).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+            let mut inspected = vec![0; monkeys.len()];
+
+            // Compare round by round, for as long as the exact levels fit
+            for _ in 0..20 {
+                if exact_round(&mut items, &monkeys, &mut inspected).is_none() {
+                    break;
+                }
+
+                play_rounds2(&mut reduced, 1, reducer);
+                prop_assert_eq!(reduced.iter().map(|m| m.inspected).collect::<Vec<_>>(), inspected.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::io::prelude::*;
+use std::collections::VecDeque;
+use tracing::trace;
+use common::geometry::Point2;
+use common::grid::Grid;
+use common::raster::{gradient, Image, Rgb};
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+const START: u8 = 0;
+const END: u8 = 27;
+
+type Point = Point2<usize>;
+
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    // Height, and how far it is from the end once the search gets to it
+    grid: Grid<(u8, Option<usize>)>,
+    end: Point,
+}
+
+impl Map {
+    fn neighbors(&self, p: &Point) -> Vec<Point> {
+        self.grid.neighbours4(*p)
+            .filter(|n| self.get(n).0 + 1 >= self.get(p).0)
+            .collect()
+    }
+
+    fn set_route(&mut self, p: &Point, shortest_route: usize) {
+        self.grid[*p].1 = Some(shortest_route);
+    }
+
+    fn get(&self, p: &Point) -> (u8, Option<usize>) {
+        self.grid[*p]
+    }
+
+    // Breadth first from `from`, going down at most one step at a time and
+    // noting how far each square is, until it reaches one no higher than
+    // `start_val`. Returns how far that one is.
+    fn find_shortest_route(&mut self, from: &Point, start_val: u8) -> Option<usize> {
+        let mut queue = VecDeque::from([*from]);
+        self.set_route(from, 0);
+
+        while let Some(p) = queue.pop_front() {
+            let (val, path) = self.get(&p);
+            let path = path.unwrap();
+            trace!(x = p.x, y = p.y, path, "Reached");
+            if val <= start_val {
+                return Some(path);
+            }
+
+            for n in self.neighbors(&p) {
+                if self.get(&n).1.is_none() {
+                    self.set_route(&n, path + 1);
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+pub type Input = Map;
+
+fn parse_char(c: char, column: usize) -> Result<u8, ParseError> {
+    match c {
+        'S' => Ok(START),
+        'E' => Ok(END),
+        'a'..='z' => Ok(c as u8 - b'a' + 1),
+        c => Err(ParseError::new(column, "a height (a-z), S or E", c.to_string())),
+    }
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let grid = Grid::parse(parse::lines(reader).collect::<Result<Vec<_>, _>>()?, |c, column| {
+        Ok((parse_char(c, column)?, None))
+    })?;
+
+    let find = |v: u8, name: &str| {
+        grid.points()
+            .find(|p| grid[*p].0 == v)
+            .ok_or_else(|| ParseError::new(1, format!("a map containing {}", name), "").at_line(grid.height() + 1))
+    };
+
+    find(START, "the start (S)")?;
+    let end = find(END, "the end (E)")?;
+
+    Ok(Map { grid, end })
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// Steps from the end down to the nearest square no higher than `start_val`
+fn route_answer(input: &Input, start_val: u8) -> Answer {
+    let mut map : Input = input.clone();
+    match map.find_shortest_route(&input.end, start_val) {
+        Some(route) => route.into(),
+        None => "Not found!".into(),
+    }
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    route_answer(input, START)
+}
+
+
+pub fn part_2(input: &Input) -> Answer {
+    route_answer(input, 1)
+}
+
+/// The height map, low ground dark and high ground light, with part 1's
+/// route from S to E drawn over it
+pub fn render(input: &Input) -> Image {
+    let mut map = input.clone();
+    map.find_shortest_route(&input.end, START);
+
+    // Follow the distances to the end the search left behind, downhill
+    let mut route = Vec::new();
+    let mut p = map.grid.points().find(|p| map.get(p).0 == START);
+    while let Some(current) = p {
+        route.push(current);
+        p = match map.get(&current).1 {
+            Some(dist) if dist > 0 => map.grid.neighbours4(current)
+                .find(|n| map.get(n).1 == Some(dist - 1) && map.get(&current).0 + 1 >= map.get(n).0),
+            _ => None,
+        };
+    }
+
+    let mut palette = gradient(Rgb(20, 60, 20), Rgb(240, 240, 240), END as usize + 1);
+    palette.push(Rgb(220, 30, 30));
+
+    let mut pixels = Grid::from_vec(map.grid.width(), map.grid.iter().map(|(h, _)| *h).collect());
+    for p in route {
+        pixels[p] = END + 1;
+    }
+    Image::new(pixels, &palette).scaled(4)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(31));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(29));
+    }
+
+    #[test]
+    fn render_example() {
+        let image = render(&parse(EXAMPLE).unwrap());
+        let route = image.pixels.iter().filter(|&&i| i == END + 1).count();
+        assert_eq!(route, (31 + 1) * 4 * 4);
+    }
+}
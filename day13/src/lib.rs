@@ -0,0 +1,252 @@
+use std::io::prelude::*;
+use std::str;
+use std::cmp::Ordering;
+use std::iter::once;
+use tracing::trace;
+use common::{parse, Answer, NoConfig, ParseError, Part, Run, Solution};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+#[derive(Debug, Clone)]
+pub enum Packet {
+    Value(u8),
+    List(Vec<Packet>),
+}
+
+const OPEN :u8 = b'[';
+const CLOSE :u8 = b']';
+const COMMA :u8 = b',';
+
+// Deeper lists are rejected rather than risking a stack overflow
+const MAX_DEPTH :usize = 256;
+
+fn unexpected(l: &[u8], p: usize, expected: &str) -> ParseError {
+    let found = l.get(p..p+1).map_or("".into(), String::from_utf8_lossy);
+    ParseError::new(p + 1, expected, found)
+}
+
+impl Packet {
+    // Parse a whole line, which must hold exactly one list
+    fn parse(l: &str) -> Result<Packet, ParseError> {
+        let l = l.as_bytes();
+        if l.first() != Some(&OPEN) {
+            return Err(unexpected(l, 0, "'['"));
+        }
+
+        let mut p = 1;
+        let packet = Packet::from_line(l, &mut p, 1)?;
+        if p < l.len() {
+            return Err(unexpected(l, p, "end of line"));
+        }
+
+        Ok(packet)
+    }
+
+    // Parse the rest of a list whose opening bracket is just before `p`,
+    // nested `depth` lists deep
+    fn from_line(l: &[u8], p: &mut usize, depth: usize) -> Result<Packet, ParseError> {
+        if depth > MAX_DEPTH {
+            return Err(unexpected(l, *p - 1, &format!("lists nested at most {} deep", MAX_DEPTH)));
+        }
+
+        let mut contents = Vec::new();
+
+        if l.get(*p) == Some(&CLOSE) {
+            *p += 1;
+            return Ok(Packet::List(contents));
+        }
+
+        loop {
+            // An item - either a nested list or a number
+            if l.get(*p) == Some(&OPEN) {
+                *p += 1;
+                contents.push(Packet::from_line(l, p, depth + 1)?);
+            } else if l.get(*p).is_some_and(|c| c.is_ascii_digit()) {
+                // Find the end of the number
+                let s = *p;
+                while *p < l.len() && l[*p].is_ascii_digit() {
+                    *p += 1;
+                }
+
+                let n = str::from_utf8(&l[s..*p]).unwrap();
+                contents.push(Packet::Value(parse::number(n, s + 1)?));
+            } else {
+                return Err(unexpected(l, *p, "'[' or a number"));
+            }
+
+            // Followed by either another item or the end of the list
+            match l.get(*p) {
+                Some(&COMMA) => *p += 1,
+                Some(&CLOSE) => {
+                    *p += 1;
+                    return Ok(Packet::List(contents));
+                },
+                _ => return Err(unexpected(l, *p, "',' or ']'")),
+            }
+        }
+    }
+
+    fn as_list(&self) -> Packet {
+        Packet::List(vec![self.clone()])
+    }
+}
+
+impl Eq for Packet {}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self {
+            Packet::Value(x) => {
+                match other {
+                    Packet::Value(y) => x.cmp(y),
+                    Packet::List(_) => self.as_list().cmp(other),
+                }
+            },
+            Packet::List(sl) => {
+                match other {
+                    Packet::Value(_) => self.cmp(&other.as_list()),
+                    Packet::List(ol) => {
+                        for i in 0..usize::min(sl.len(), ol.len()) {
+                            if sl[i] < ol[i] {
+                                return Ordering::Less;
+                            }
+
+                            if ol[i] < sl[i] {
+                                return Ordering::Greater;
+                            }
+
+                            // Equal items, continue
+                        }
+
+                        if sl.len() < ol.len() {
+                            Ordering::Less
+                        } else if sl.len() > ol.len() {
+                            Ordering::Greater
+                        } else {
+                            Ordering::Equal
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+pub type Input = Vec<(Packet,Packet)>;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let lines = parse::lines(reader).collect::<Result<Vec<_>, _>>()?;
+
+    // Pairs of packets, separated by a blank line
+    let packet = |i: usize| {
+        let l = lines.get(i).map_or("", |l| l.as_str());
+        Packet::parse(l).map_err(|e| e.at_line(i + 1))
+    };
+
+    (0..lines.len())
+        .step_by(3)
+        .map(|i| {
+            if lines.get(i + 2).is_some_and(|l| !l.trim().is_empty()) {
+                return Err(ParseError::new(1, "a blank line", lines[i + 2].as_str()).at_line(i + 3));
+            }
+
+            Ok((packet(i)?, packet(i + 1)?))
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+pub fn part_1(input: &Input) -> Answer {
+    input.iter().enumerate()
+        .inspect(|(i, p)| trace!(pair = i + 1, ordering = ?p.0.cmp(&p.1)))
+        .filter(|(_,p)| p.0 < p.1)
+        .map(|(i,_)| i+1)
+        .sum::<usize>()
+        .into()
+}
+
+
+pub fn part_2(input: &Input) -> Answer {
+    let mut input = input.iter()
+        .flat_map(|x| once(x.0.clone()).chain(once(x.1.clone())))
+        .collect::<Vec<_>>();
+
+    let del1 = Packet::Value(2).as_list().as_list();
+    let del2 = Packet::Value(6).as_list().as_list();
+    input.push(del1.clone());
+    input.push(del2.clone());
+    input.sort();
+
+    input.iter().enumerate()
+        .filter(|(_, p)| **p == del1 || **p == del2)
+        .map(|(i,_)| i + 1)
+        .product::<usize>()
+        .into()
+}
+
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Input;
+    type Config = NoConfig;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, _: &NoConfig) -> Answer {
+        part_1(input)
+    }
+
+    fn part2(input: &Input, _: &NoConfig) -> Answer {
+        part_2(input)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&part_1, &part_2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("[1,[2]\n[1]\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:1:7: expected ',' or ']', found end of line");
+        let e = parse("[1]\n[1]\nx\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 1, "x"));
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input), Answer::Int(13));
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input), Answer::Int(140));
+    }
+}
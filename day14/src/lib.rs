@@ -0,0 +1,362 @@
+use std::io::prelude::*;
+use std::collections::HashSet;
+use std::iter;
+use tracing::{debug, trace};
+use common::anim::Frame;
+use common::geometry::{Bounds, Dir8, Point2, Vec2};
+use common::grid::Grid;
+use common::raster::{Image, Rgb};
+use serde::Deserialize;
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+type Point = Point2<isize>;
+
+// Well beyond anything in the puzzle (rock goes down to about 170), but
+// keeps rock lines from growing without bound and part 2's pile of sand,
+// which grows with the square of the depth, small enough to pour
+const MAX_COORD: isize = 1000;
+
+fn coordinate(s: &str, column: usize) -> Result<isize, ParseError> {
+    match parse::number(s, column)? {
+        n if (0..=MAX_COORD).contains(&n) => Ok(n),
+        _ => Err(ParseError::new(column, format!("a coordinate from 0 to {}", MAX_COORD), s)),
+    }
+}
+
+// `column` is where `s` starts in its line
+fn parse_point(s: &str, column: usize) -> Result<Point, ParseError> {
+    let (x, y) = s.split_once(',')
+        .ok_or_else(|| ParseError::new(column, "x,y", s))?;
+
+    Ok(Point::new(
+        coordinate(x, column)?,
+        coordinate(y, column + parse::column_of(s, y) - 1)?,
+    ))
+}
+
+pub type Input = HashSet<Point>;
+
+/// Where the sand comes from, as `[x, y]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub source: (isize, isize),
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { source: (500, 0) }
+    }
+}
+
+// Sand can come from anywhere rock can be
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        let (x, y) = self.source;
+        if ![x, y].iter().all(|c| (0..=MAX_COORD).contains(c)) {
+            return Err(format!("source must be from [0, 0] to [{}, {}], not [{}, {}]", MAX_COORD, MAX_COORD, x, y));
+        }
+
+        Ok(())
+    }
+}
+
+impl Config {
+    fn source(&self) -> Point {
+        Point::new(self.source.0, self.source.1)
+    }
+}
+
+// Two below the lowest rock (or the source, should that be lower)
+fn abyss(input: &Input, source: Point) -> isize {
+    Bounds::from_points(input.iter().chain([&source])).unwrap().max.y + 2
+}
+
+// All points on the (horizontal or vertical) segment from `p1` to `p2`
+fn line(p1: &Point, p2: &Point) -> HashSet<Point> {
+    let step = (*p2 - *p1).signum();
+    let mut p = *p1;
+    let mut res = HashSet::from([p]);
+    while p != *p2 {
+        p += step;
+        res.insert(p);
+    }
+
+    res
+}
+
+fn rock_from_str(s: &str) -> Result<HashSet<Point>, ParseError> {
+    let points = s.split(" -> ")
+        .map(|p| parse_point(p, parse::column_of(s, p)).map(|pt| (pt, p)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Rock paths are made of horizontal and vertical segments only
+    if let Some((_, (_, p))) = points.iter().zip(points.iter().skip(1))
+        .find(|((p1, _), (p2, _))| p1.x != p2.x && p1.y != p2.y) {
+        return Err(ParseError::new(parse::column_of(s, p), "a point in line with the previous one", *p));
+    }
+
+    Ok(points.iter().zip(points.iter().skip(1))
+        .map(|((p1, _), (p2, _))| line(p1, p2))
+        .fold(HashSet::new(), |acc, hs| acc.union(&hs).cloned().collect()))
+}
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    let rocks = parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| rock_from_str(&l).map_err(|e| e.at_line(i + 1))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if rocks.is_empty() {
+        return Err(ParseError::new(1, "a rock path", "").at_line(1));
+    }
+
+    Ok(rocks.into_iter().flatten().collect())
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// Where sand at `s` falls next, if it isn't stuck
+fn fall(blocked: &HashSet<Point>, s: Point) -> Option<Point> {
+    [Dir8::Down, Dir8::DownLeft, Dir8::DownRight].into_iter()
+        .map(|d| s + d.vec())
+        .find(|p| !blocked.contains(p))
+}
+
+// Where the next unit of sand comes to rest. `lowest_rock` is two below the
+// lowest rock: with `floor` set that's where the floor is, otherwise sand
+// that gets there falls into the abyss and never rests.
+fn resting_place(blocked: &HashSet<Point>, source: Point, lowest_rock: isize, floor: bool) -> Option<Point> {
+    let mut s = source;
+
+    loop {
+        assert!(s.y < lowest_rock);
+
+        if s.y == lowest_rock-1 {
+            return if floor { Some(s) } else { None };
+        }
+
+        match fall(blocked, s) {
+            Some(p)=> s = p,
+            None=> return Some(s),
+        }
+    }
+}
+
+// Where sand comes to rest before the source is blocked or sand starts
+// falling into the abyss
+fn pour_sand(input: &Input, source: Point, floor: bool) -> HashSet<Point> {
+    let mut blocked = input.clone();
+
+    let lowest_rock = abyss(input, source);
+
+    debug!("Lowest rock is at y = {}", lowest_rock);
+
+    loop {
+        if blocked.contains(&source) {
+            break;
+        }
+
+        match resting_place(&blocked, source, lowest_rock, floor) {
+            Some(p) => {
+                trace!("Resting place {:?}", p);
+                blocked.insert(p);
+            },
+            None => break,
+        }
+    }
+
+    blocked.difference(input).copied().collect()
+}
+
+pub fn part_1(input: &Input, config: &Config) -> Answer {
+    pour_sand(input, config.source(), false).len().into()
+}
+
+
+pub fn part_2(input: &Input, config: &Config) -> Answer {
+    pour_sand(input, config.source(), true).len().into()
+}
+
+/// The cave once the sand has stopped, as in part 1 or part 2 (`floor`):
+/// rock grey, sand yellow and the source red
+pub fn render(input: &Input, config: &Config, floor: bool) -> Image {
+    let source = config.source();
+    let sand = pour_sand(input, source, floor);
+    let lowest_rock = abyss(input, source);
+
+    // A bit of air around everything, down to the floor if there is one
+    let mut bounds = Bounds::from_points(input.iter().chain(sand.iter()).chain([&source])).unwrap();
+    bounds.min += Vec2::new(-1, -1);
+    bounds.max += Vec2::new(1, 1);
+    if floor {
+        bounds.max.y = lowest_rock;
+    }
+
+    let width = (bounds.max.x - bounds.min.x + 1) as usize;
+    let height = (bounds.max.y - bounds.min.y + 1) as usize;
+    let mut pixels = Grid::new(width, height, 0);
+    let mut paint = |p: &Point, colour| {
+        pixels[Point2::new((p.x - bounds.min.x) as usize, (p.y - bounds.min.y) as usize)] = colour;
+    };
+
+    for p in input.iter() {
+        paint(p, 1);
+    }
+    if floor {
+        for x in bounds.min.x..=bounds.max.x {
+            paint(&Point::new(x, lowest_rock), 1);
+        }
+    }
+    for p in sand.iter() {
+        paint(p, 2);
+    }
+    paint(&source, 3);
+
+    let palette = [Rgb(20, 20, 40), Rgb(120, 120, 120), Rgb(230, 200, 90), Rgb(220, 30, 30)];
+    Image::new(pixels, &palette).scaled(3)
+}
+
+/// Sand falling a step at a time, until it stops coming to rest the way it
+/// does in part 1 (into the abyss) or part 2 (`floor`, blocking the source)
+pub fn steps<'a>(input: &'a Input, config: &Config, floor: bool) -> impl Iterator<Item = Frame> + 'a {
+    let source = config.source();
+    let lowest_rock = abyss(input, source);
+    let mut blocked = input.clone();
+    let mut grain: Option<Point> = None;
+    let mut rested = 0;
+
+    let mut start = Frame::new(source, "Start");
+    for p in input.iter() {
+        start.set(*p, '#');
+    }
+    start.set(source, '+');
+
+    let mut done = false;
+    let sand = iter::from_fn(move || {
+        if done {
+            return None;
+        }
+
+        let mut frame = Frame::new(source, "");
+        match grain {
+            None if blocked.contains(&source) => return None,
+            None => {
+                grain = Some(source);
+                frame.set(source, 'o');
+            },
+            Some(s) if s.y == lowest_rock - 1 && !floor => {
+                frame.set(s, ' ');
+                frame.focus = s;
+                frame.caption = format!("Sand falls into the abyss, {} units of sand at rest", rested);
+                done = true;
+                return Some(frame);
+            },
+            Some(s) => {
+                let next = if s.y == lowest_rock - 1 { None } else { fall(&blocked, s) };
+                match next {
+                    Some(p) => {
+                        frame.set(s, if s == source { '+' } else { ' ' });
+                        frame.set(p, 'o');
+                        frame.focus = p;
+                        grain = Some(p);
+                    },
+                    None => {
+                        blocked.insert(s);
+                        rested += 1;
+                        grain = None;
+                        frame.focus = s;
+                        if s.y == lowest_rock - 1 {
+                            // Only draw as much of the floor as there's sand on
+                            frame.text(Point::new(s.x - 1, lowest_rock), "###");
+                        }
+                    },
+                }
+            },
+        }
+
+        frame.caption = format!("{} units of sand at rest", rested);
+        Some(frame)
+    });
+
+    iter::once(start).chain(sand)
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &Config::default()), Answer::Int(24));
+    }
+
+    #[test]
+    fn parse_error() {
+        let e = parse("498,4 -> 498,6\n500,100000 -> 501,100000\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:2:5: expected a coordinate from 0 to 1000, found \"100000\"");
+        assert_eq!(parse("498,4 -> 500,6").unwrap_err().column, 10);
+    }
+
+    #[test]
+    fn config_error() {
+        assert!(Config { source: (500, isize::MAX) }.validate().is_err());
+        assert!(Config { source: (isize::MAX, 0) }.validate().is_err());
+        assert!(Config { source: (-1, 0) }.validate().is_err());
+        assert!(Config { source: (MAX_COORD, MAX_COORD) }.validate().is_ok());
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_2(&input, &Config::default()), Answer::Int(93));
+    }
+
+    #[test]
+    fn render_example() {
+        let input = parse(EXAMPLE).unwrap();
+        let image = render(&input, &Config::default(), true);
+        let sand = image.pixels.iter().filter(|&&i| i == 2).count();
+        // The source is covered in sand too, but is drawn over it
+        assert_eq!(sand, (93 - 1) * 3 * 3);
+    }
+
+    #[test]
+    fn steps_end_like_the_parts() {
+        let input = parse(EXAMPLE).unwrap();
+        let last = steps(&input, &Config::default(), false).last().unwrap();
+        assert_eq!(last.caption, "Sand falls into the abyss, 24 units of sand at rest");
+        let last = steps(&input, &Config::default(), true).last().unwrap();
+        assert_eq!(last.caption, "93 units of sand at rest");
+    }
+}
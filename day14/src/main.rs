@@ -1,124 +1,24 @@
-use std::io::prelude::*;
-use std::io::BufReader;
-use std::fs::File;
-use std::collections::HashSet;
+use common::{print_answers, InputSource, Params, Part};
+use std::process;
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash)]
-struct Point {
-    x: isize,
-    y: isize,
-}
-
-impl Point {
-    fn new(x: isize, y:isize) -> Point {
-        Point{x,y}
-    }
-
-    fn from_str(s: &str) -> Point {
-        let parts = s.split(",")
-            .map(|n| n.parse::<isize>().unwrap())
-            .collect::<Vec<_>>();
-
-        Point {x: parts[0], y: parts[1] }
-    }
-}
-
-type Input = HashSet<Point>;
-
-fn line(p1: &Point, p2: &Point) -> HashSet<Point> {
-    if p1.x == p2.x {
-        let l = isize::min(p1.y, p2.y);
-        let u = isize::max(p1.y, p2.y);
-        (l..=u)
-            .map(|y| Point::new(p1.x, y))
-            .collect()
-    } else {
-        assert!(p1.y == p2.y);
-        let l = isize::min(p1.x, p2.x);
-        let u = isize::max(p1.x, p2.x);
-        (l..=u)
-            .map(|x| Point::new(x, p1.y))
-            .collect()
-    }
-}
-
-fn rock_from_str(s: &str) -> HashSet<Point> {
-    let points = s.split(" -> ")
-        .map(|p| Point::from_str(p))
-        .collect::<Vec<_>>();
-
-    points.iter().zip(points.iter().skip(1))
-        .map(|(p1, p2)| line(p1, p2))
-        .fold(HashSet::new(), |acc, hs| acc.union(&hs).cloned().collect())
-}
-
-fn read_input() -> Input {
-    let input_file = File::open("src/input.txt").unwrap();
-    BufReader::new(input_file)
-        .lines()
-        .flat_map(|l| l.ok())
-        .flat_map(|l| rock_from_str(&l))
-        .collect()
-}
-
-fn resting_place(blocked: &HashSet<Point>, lowest_rock: isize) -> Option<Point> {
-    let mut s = Point::new(500,0);
-
-    loop {
-        assert!(s.y < lowest_rock);
-
-        if s.y == lowest_rock-1 {
-            return Some(s);
-        }
-
-        let new_s = vec![
-            Point::new(s.x, s.y+1),
-            Point::new(s.x-1, s.y+1),
-            Point::new(s.x+1, s.y+1),].into_iter()
-                .filter(|p| !blocked.contains(p))
-                .nth(0);
-
-        match new_s {
-            Some(p)=> s = p,
-            None=> return Some(s),
-        }
-    }
-}
-
-fn part_1(input: &Input) {
-    let mut blocked = input.clone();
-
-    let lowest_rock = input.iter()
-        .map(|p| p.y)
-        .max()
-        .unwrap() + 2;
-
-    println!("Lowest rock is at y = {}", lowest_rock);
-
-    loop {
-        if blocked.contains(&Point::new(500,0)) {
-            break;
-        }
-
-        match resting_place(&blocked, lowest_rock) {
-            Some(p) => {
-                println!("Resting place {:?}", p);
-                blocked.insert(p);
-            },
-            None => break,
+fn main() {
+    common::init_tracing(0);
+
+    let source = InputSource::from_args(day14::DEFAULT_INPUT);
+    let config = Params::for_input(&source).and_then(|p| p.get()).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    let mut reader = source.open().unwrap_or_else(|e| {
+        eprintln!("Can't open {}: {}", source, e);
+        process::exit(1);
+    });
+
+    match day14::run(&mut reader, &config, Part::All) {
+        Ok(run) => print_answers(&run.results),
+        Err(e) => {
+            eprintln!("{}", e.with_file(&source));
+            process::exit(1);
         }
     }
-
-    println!("Part 1: {}", blocked.len() - input.len());
-}
-
-
-fn part_2(input: &Input) {
-}
-
-
-fn main() {
-    let input = read_input();
-    part_1(&input);
-    part_2(&input);
 }
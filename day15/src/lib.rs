@@ -0,0 +1,473 @@
+use std::io::prelude::*;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use regex::Regex;
+use lazy_static::lazy_static;
+use tracing::{debug, trace};
+use common::geometry::Point2;
+use common::grid::Grid;
+use common::raster::{Image, Rgb};
+use serde::Deserialize;
+use common::explore::{arg, Explore};
+use common::{parse, Answer, ParseError, Part, Run, Solution, Validate};
+
+pub const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.txt");
+
+// Far beyond anything in the puzzle (a few million), but keeps ranges and
+// spans from overflowing
+const MAX_COORD: isize = 1_000_000_000;
+
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    loc: Point2<isize>,
+    beacon: Point2<isize>,
+}
+
+impl Sensor {
+    fn from_line(l: &str) -> Result<Sensor, ParseError> {
+        lazy_static! {
+            static ref PARSER_RE: Regex =
+                Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$").unwrap();
+        }
+
+        let cap = PARSER_RE.captures(l).ok_or_else(|| mismatch(l))?;
+        let num = |i| {
+            let m = cap.get(i).unwrap();
+            match parse::number::<isize>(m.as_str(), m.start() + 1)? {
+                n if (-MAX_COORD..=MAX_COORD).contains(&n) => Ok(n),
+                _ => Err(ParseError::new(m.start() + 1, format!("a coordinate from -{} to {}", MAX_COORD, MAX_COORD), m.as_str())),
+            }
+        };
+
+        Ok(Sensor{
+            loc: Point2::new(num(1)?, num(2)?),
+            beacon: Point2::new(num(3)?, num(4)?),
+        })
+    }
+
+    // How far the sensor can tell there's no (other) beacon
+    fn range(&self) -> isize {
+        self.loc.manhattan(&self.beacon)
+    }
+
+    fn no_beacon_at_span(&self, line: isize) -> Option<(isize, isize)> {
+        // Lines can be anywhere, sensors can't
+        let dist = self.range();
+        let line_dist = line.abs_diff(self.loc.y);
+        if line_dist > dist as usize {
+            return None;
+        }
+
+        let rem = dist - line_dist as isize;
+        Some((self.loc.x-rem, self.loc.x+rem))
+    }
+}
+
+// Where a line that isn't a sensor first goes wrong. The line's walked
+// through the same pieces the regex has, an empty one standing for a number.
+fn mismatch(l: &str) -> ParseError {
+    const PIECES: [&str; 8] = ["Sensor at x=", "", ", y=", "", ": closest beacon is at x=", "", ", y=", ""];
+
+    let mut rest = l;
+    for piece in PIECES {
+        let column = l.len() - rest.len() + 1;
+        if piece.is_empty() {
+            let sign = usize::from(rest.starts_with('-'));
+            let digits = rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+            if digits == 0 {
+                return ParseError::new(column, "a number", rest);
+            }
+            rest = &rest[sign + digits..];
+        } else {
+            let same = rest.bytes().zip(piece.bytes()).take_while(|(a, b)| a == b).count();
+            if same < piece.len() {
+                return ParseError::new(column + same, format!("{:?}", &piece[same..]), &rest[same..]);
+            }
+            rest = &rest[same..];
+        }
+    }
+
+    ParseError::new(l.len() - rest.len() + 1, "end of line", rest)
+}
+
+#[derive(Debug, Clone)]
+struct Spans {
+    spans: Vec<(isize, isize)>,
+    min: isize,
+    max: isize,
+}
+
+impl Spans {
+    fn new(min: isize, max: isize) -> Spans {
+        Spans { spans: Vec::new(), min, max }
+    }
+
+    fn add(&mut self, span: (isize, isize)) {
+        // Nothing to clamp if it's out of bounds altogether
+        if span.1 < self.min || span.0 > self.max {
+            return;
+        }
+
+        let span = (span.0.clamp(self.min, self.max), span.1.clamp(self.min, self.max));
+        self.spans.push(span);
+    }
+
+    fn compact(&mut self) {
+        // Sort spans by start point, descending - that way we can iterate from
+        // lowest to highest by popping from the end (to avoid costly vector
+        // operations)
+        self.spans.sort_by_key(|span| Reverse(span.0));
+
+        let mut compact = Vec::new();
+        let mut prev = match self.spans.pop() {
+            Some(span) => span,
+            None => return,
+        };
+        while let Some(current) = self.spans.pop() {
+            // Spans overlap. Merge them and continue
+            if current.0 <= prev.1.saturating_add(1) {
+                prev.1 = isize::max(prev.1, current.1);
+                trace!(?current, merged = ?prev, "Merging spans");
+                continue;
+            }
+
+            // Spans don't overlay - push the previous one and continue
+            // with the current one
+            compact.push(prev);
+            prev = current;
+        }
+
+        compact.push(prev);
+        self.spans = compact;
+    }
+}
+
+pub type Input = Vec<Sensor>;
+
+/// The line part 1 counts along, and how far part 2's search area goes from
+/// 0, both ways. The example uses 10 and 20.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub interesting_line: isize,
+    pub max: isize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config { interesting_line: 2000000, max: 4000000 }
+    }
+}
+
+// Neither needs to go beyond where sensors and beacons can be, and that
+// keeps the tuning frequency from overflowing
+impl Validate for Config {
+    fn validate(&self) -> Result<(), String> {
+        if !(0..=MAX_COORD).contains(&self.max) {
+            return Err(format!("max must be from 0 to {}, not {}", MAX_COORD, self.max));
+        }
+        if !(-MAX_COORD..=MAX_COORD).contains(&self.interesting_line) {
+            return Err(format!("interesting_line must be from -{} to {}, not {}", MAX_COORD, MAX_COORD, self.interesting_line));
+        }
+
+        Ok(())
+    }
+}
+
+// The puzzle's tuning frequency multiplies the distress beacon's x by this,
+// whatever the size of the search area
+const TUNING_MULTIPLIER: isize = 4000000;
+
+fn tuning_frequency(beacon: Point2<isize>) -> isize {
+    beacon.x * TUNING_MULTIPLIER + beacon.y
+}
+
+// Rendered images are at most this many pixels along each side
+const RENDER_SIZE: usize = 800;
+
+pub fn read_input<R: BufRead>(reader: R) -> Result<Input, ParseError> {
+    parse::lines(reader)
+        .enumerate()
+        .map(|(i, l)| l.and_then(|l| Sensor::from_line(&l).map_err(|e| e.at_line(i + 1))))
+        .collect()
+}
+
+pub fn parse(input: &str) -> Result<Input, ParseError> {
+    read_input(input.as_bytes())
+}
+
+// The parts of `line` between `min` and `max` that sensors cover, merged
+fn covered(input: &Input, line: isize, min: isize, max: isize) -> Vec<(isize, isize)> {
+    let mut spans = input.iter()
+        .flat_map(|s| s.no_beacon_at_span(line))
+        .fold(Spans::new(min, max), |mut acc, span| { acc.add(span); acc });
+
+    spans.compact();
+    spans.spans
+}
+
+// Positions on `line` that can't hold a beacon
+fn no_beacon_count(input: &Input, line: isize) -> isize {
+    let sum = covered(input, line, isize::MIN, isize::MAX).iter()
+        .map(|s| (s.1-s.0+1).abs())
+        .sum::<isize>();
+    let beacons_on_line = input.iter()
+        .filter(|s| s.beacon.y == line)
+        .map(|s| s.beacon)
+        .collect::<HashSet<_>>()
+        .len();
+
+    sum - beacons_on_line as isize
+}
+
+// The only spot within 0..=max that no sensor covers, where the distress
+// beacon must be
+fn distress_beacon(input: &Input, max: isize) -> Option<Point2<isize>> {
+    for line in 0..=max {
+        let mut spans = input.iter()
+            .flat_map(|s| s.no_beacon_at_span(line))
+            .fold(Spans::new(0, max), |mut acc, span| { acc.add(span); acc });
+
+        spans.compact();
+        trace!(line, spans = ?spans.spans);
+        // The first gap, should there be more than one
+        if spans.spans.len() > 1 {
+            debug!(line, spans = ?spans.spans, "Found the gap");
+            return Some(Point2::new(spans.spans[0].1 + 1, line));
+        }
+    }
+
+    None
+}
+
+pub fn part_1(input: &Input, config: &Config) -> Answer {
+    no_beacon_count(input, config.interesting_line).into()
+}
+
+pub fn part_2(input: &Input, config: &Config) -> Answer {
+    match distress_beacon(input, config.max) {
+        Some(beacon) => tuning_frequency(beacon).into(),
+        None => "Not found!".into(),
+    }
+}
+
+/// Part 2's search area, scaled down: each pixel coloured after the first
+/// sensor covering its middle (black if none does), sensors and beacons in
+/// white and the gap the distress beacon is in circled red
+pub fn render(input: &Input, config: &Config) -> Image {
+    let max = config.max;
+    let size = RENDER_SIZE.min(max as usize + 1);
+    let scale = (max + 1) as f64 / size as f64;
+    let to_pixel = |p: Point2<isize>| {
+        let area = 0..=max;
+        (area.contains(&p.x) && area.contains(&p.y))
+            .then(|| Point2::new((p.x as f64 / scale) as usize, (p.y as f64 / scale) as usize))
+    };
+
+    let hues = [
+        Rgb(60, 90, 160), Rgb(70, 140, 90), Rgb(150, 110, 60), Rgb(120, 70, 140),
+        Rgb(60, 140, 140), Rgb(150, 150, 70), Rgb(150, 70, 90), Rgb(90, 90, 90),
+    ];
+    let (red, white) = (hues.len() as u8 + 1, hues.len() as u8 + 2);
+    let mut palette = vec![Rgb(0, 0, 0)];
+    palette.extend(hues);
+    palette.extend([Rgb(255, 40, 40), Rgb(255, 255, 255)]);
+
+    let mut pixels = Grid::new(size, size, 0);
+    for p in pixels.points().collect::<Vec<_>>() {
+        let middle = Point2::new(((p.x as f64 + 0.5) * scale) as isize, ((p.y as f64 + 0.5) * scale) as isize);
+        if let Some(i) = input.iter().position(|s| s.loc.manhattan(&middle) <= s.range()) {
+            pixels[p] = 1 + (i % hues.len()) as u8;
+        }
+    }
+
+    if let Some(beacon) = distress_beacon(input, max) {
+        let gap = to_pixel(beacon).unwrap();
+        let ring = (-3..=3isize).flat_map(|dy| (-3..=3isize).map(move |dx| (dx, dy)))
+            .filter(|(dx, dy)| dx.abs().max(dy.abs()) >= 2);
+        for (dx, dy) in ring {
+            if let (Some(x), Some(y)) = (gap.x.checked_add_signed(dx), gap.y.checked_add_signed(dy)) {
+                if let Some(c) = pixels.get_mut(Point2::new(x, y)) {
+                    *c = red;
+                }
+            }
+        }
+    }
+
+    for p in input.iter().flat_map(|s| [s.loc, s.beacon]).filter_map(to_pixel) {
+        pixels[p] = white;
+    }
+
+    Image::new(pixels, &palette)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Input;
+    type Config = Config;
+
+    fn parse(input: &str) -> Result<Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(input: &Input, config: &Config) -> Answer {
+        part_1(input, config)
+    }
+
+    fn part2(input: &Input, config: &Config) -> Answer {
+        part_2(input, config)
+    }
+}
+
+pub fn run(reader: &mut dyn BufRead, config: &Config, part: Part) -> Result<Run, ParseError> {
+    common::solve(|| read_input(reader), part, [&|i| part_1(i, config), &|i| part_2(i, config)])
+}
+
+/// The sensors, for `aoc repl`: `span` says what they cover on a line
+pub struct Explorer {
+    input: Input,
+    max: isize,
+}
+
+impl Explorer {
+    pub fn new(input: &Input, config: &Config) -> Explorer {
+        Explorer { input: input.clone(), max: config.max }
+    }
+}
+
+fn show_spans(spans: &[(isize, isize)]) -> String {
+    spans.iter().map(|s| format!("{}..={}", s.0, s.1)).collect::<Vec<_>>().join(", ")
+}
+
+impl Explore for Explorer {
+    fn help(&self) -> Vec<(&'static str, &'static str)> {
+        vec![("span LINE", "what the sensors cover on a line, all told and within part 2's search area")]
+    }
+
+    fn command(&mut self, words: &[&str]) -> Result<String, String> {
+        if words[0] != "span" {
+            return Err(format!("Unknown command {:?}", words[0]));
+        }
+
+        let line = arg(words, 1, None)?;
+        Ok(format!("Covered: {}\nNo beacon at {} positions\nWithin 0..={}: {}",
+                   show_spans(&covered(&self.input, line, isize::MIN, isize::MAX)),
+                   no_beacon_count(&self.input, line),
+                   self.max,
+                   show_spans(&covered(&self.input, line, 0, self.max))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use common::Params;
+    use proptest::prelude::*;
+
+    const EXAMPLE: &str = include_str!("example.txt");
+
+    #[test]
+    fn parse_error() {
+        let e = parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=1x\n").unwrap_err();
+        assert_eq!(e.with_file("input.txt").to_string(), "input.txt:1:52: expected end of line, found \"x\"");
+
+        let e = parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16; closest beacon is at x=10, y=16\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 20, "; closest beacon is at x=10, y=16"));
+        assert_eq!(e.expected, "\": closest beacon is at x=\"");
+
+        let e = parse("Sensor at x=, y=18: closest beacon is at x=-2, y=15").unwrap_err();
+        assert_eq!((e.column, e.expected.as_str()), (13, "a number"));
+        assert!(parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=99999999999999999999").is_err());
+
+        let e = parse("Sensor at x=-9223372036854775807, y=0: closest beacon is at x=9223372036854775807, y=0").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (13, "-9223372036854775807"));
+        assert_eq!(e.expected, "a coordinate from -1000000000 to 1000000000");
+    }
+
+    #[test]
+    fn part_1_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(no_beacon_count(&input, 10), 26);
+    }
+
+    #[test]
+    fn part_2_example() {
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(distress_beacon(&input, 20), Some(Point2::new(14, 11)));
+        assert_eq!(tuning_frequency(Point2::new(14, 11)), 56000011);
+    }
+
+    #[test]
+    fn example_config() {
+        let config = Params::parse(include_str!("example.toml")).unwrap().get::<Config>().unwrap();
+        assert_eq!(config, Config { interesting_line: 10, max: 20 });
+
+        let input = parse(EXAMPLE).unwrap();
+        assert_eq!(part_1(&input, &config), Answer::Int(26));
+        assert_eq!(part_2(&input, &config), Answer::Int(56000011));
+    }
+
+    #[test]
+    fn config_error() {
+        assert!(Config { interesting_line: 10, max: isize::MAX }.validate().is_err());
+        assert!(Config { interesting_line: isize::MIN, max: 20 }.validate().is_err());
+        assert!(Config { interesting_line: 10, max: -1 }.validate().is_err());
+        assert!(Config::default().validate().is_ok());
+    }
+
+    #[test]
+    fn render_example() {
+        let input = parse(EXAMPLE).unwrap();
+        let image = render(&input, &Config { interesting_line: 10, max: 20 });
+        assert_eq!(image.pixels.width(), 21);
+
+        // The gap's at (14, 11), with the ring around it
+        assert_eq!(image.pixels[Point2::new(14, 11)], 0);
+        assert_eq!(image.pixels[Point2::new(16, 11)], 9);
+        assert_eq!(image.pixels[Point2::new(8, 7)], 10);
+    }
+
+    #[test]
+    fn explore() {
+        let mut explorer = Explorer::new(&parse(EXAMPLE).unwrap(), &Config { interesting_line: 10, max: 20 });
+        assert_eq!(explorer.command(&["span", "11"]).unwrap(),
+                   "Covered: -3..=13, 15..=25\nNo beacon at 28 positions\nWithin 0..=20: 0..=13, 15..=20");
+    }
+
+    fn span() -> impl Strategy<Value = (isize, isize)> {
+        (-40..40isize, 0..20isize).prop_map(|(start, len)| (start, start + len))
+    }
+
+    proptest! {
+        #[test]
+        fn compact_matches_brute_force(
+            min in -20..20isize,
+            size in 0..30isize,
+            spans in prop::collection::vec(span(), 0..8),
+        ) {
+            let max = min + size;
+            let mut compacted = Spans::new(min, max);
+            for s in spans.iter() {
+                compacted.add(*s);
+            }
+            compacted.compact();
+
+            // Every position covered by a span, within the bounds
+            let expected = spans.iter()
+                .flat_map(|s| s.0..=s.1)
+                .filter(|x| (min..=max).contains(x))
+                .collect::<BTreeSet<_>>();
+            let covered = compacted.spans.iter()
+                .flat_map(|s| s.0..=s.1)
+                .collect::<BTreeSet<_>>();
+            prop_assert_eq!(covered, expected);
+
+            // In order, with a gap between each span and the next
+            for pair in compacted.spans.windows(2) {
+                prop_assert!(pair[0].1 + 1 < pair[1].0, "{:?}", compacted.spans);
+            }
+        }
+    }
+}
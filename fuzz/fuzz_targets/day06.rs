@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Parsing must reject bad input with an error, never panic, and whatever
+// it accepts must solve without panicking
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = day06::read_input(data) {
+        day06::part_1(&input, &Default::default());
+        day06::part_2(&input, &Default::default());
+    }
+});
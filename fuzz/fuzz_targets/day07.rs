@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Parsing must reject bad input with an error, never panic, and whatever
+// it accepts must solve without panicking
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = day07::read_input(data) {
+        day07::part_1(&input, &Default::default());
+        day07::part_2(&input, &Default::default());
+    }
+});
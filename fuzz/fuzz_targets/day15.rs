@@ -0,0 +1,13 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+// Parsing must reject bad input with an error, never panic, and whatever
+// it accepts must solve without panicking. The example's search area keeps
+// each run quick.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = day15::read_input(data) {
+        let config = day15::Config { interesting_line: 10, max: 20 };
+        day15::part_1(&input, &config);
+        day15::part_2(&input, &config);
+    }
+});